@@ -0,0 +1,132 @@
+use solution::{Part, Solution};
+
+type Solve = fn(Part, &str) -> file_read::Result<String>;
+
+// A registered day with its `Solution` erased, so days with different
+// input and answer types can sit in one list.
+pub struct Day {
+    pub day: u8,
+    pub root: &'static str,
+    pub input: &'static str,
+    pub example: &'static str,
+    solve: Solve,
+    validate: fn(&str) -> Vec<file_read::Error>,
+}
+
+impl Day {
+    pub const fn of<S: Solution>() -> Day {
+        Day {
+            day: S::DAY,
+            root: S::ROOT,
+            input: S::INPUT,
+            example: S::EXAMPLE,
+            solve: solve::<S>,
+            validate: S::validate,
+        }
+    }
+
+    // parses `input` and returns the rendered answer for `part`
+    pub fn solve(&self, part: Part, input: &str) -> file_read::Result<String> {
+        (self.solve)(part, input)
+    }
+
+    // every problem in `input`, empty when it parses
+    pub fn validate(&self, input: &str) -> Vec<file_read::Error> {
+        (self.validate)(input)
+    }
+}
+
+fn solve<S: Solution>(part: Part, input: &str) -> file_read::Result<String> {
+    let input = S::parse(input)?;
+    Ok(match part {
+        Part::One => S::part1(&input).to_string(),
+        Part::Two => S::part2(&input).to_string(),
+    })
+}
+
+static REGISTRY: [Day; 3] = [
+    Day::of::<ex_1::Day1>(),
+    Day::of::<ex_2::Day2>(),
+    Day::of::<ex_3::Day3>(),
+];
+
+pub fn registry() -> &'static [Day] {
+    &REGISTRY
+}
+
+pub fn find(day: u8) -> Option<&'static Day> {
+    REGISTRY.iter().find(|entry| entry.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use file_read::Source;
+
+    #[test]
+    fn registry_test() {
+        let days: Vec<u8> = registry().iter().map(|entry| entry.day).collect();
+        assert_eq!(vec![1, 2, 3], days);
+        assert!(find(3).is_some());
+        assert!(find(4).is_none());
+    }
+
+    // every day must ship the inputs `aoc run` falls back to
+    #[test]
+    fn default_inputs_resolve_test() {
+        for entry in registry() {
+            for name in [entry.input, entry.example] {
+                let source = Source::resolve(entry.root, name);
+                if let Err(err) = source.read_to_string() {
+                    panic!("day {}: {}", entry.day, err);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn validate_test() {
+        let errors = find(1).unwrap().validate("R49\nF27\nL-12\nR50");
+        let lines: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+        assert_eq!(
+            vec![
+                "2: unknown direction `F`, expected L or R in `F27`",
+                "3: negative distance in `L-12`",
+            ],
+            lines
+        );
+        assert!(find(2).unwrap().validate("11-22").is_empty());
+        assert_eq!(2, find(2).unwrap().validate("11-22, 12-\n-5 # bad").len());
+        assert_eq!(1, find(3).unwrap().validate("12\nx\ny").len());
+    }
+
+    #[test]
+    fn solves_examples_test() {
+        let cases = [
+            (
+                1,
+                "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82",
+                "3",
+                "6",
+            ),
+            // past u64::MAX: one more ID, 1844674408 twice
+            (
+                2,
+                "11-22,\n18446744073709551610-18446744081844674408",
+                "18446744081844674441",
+                "18446744081844674441",
+            ),
+            (
+                3,
+                "987654321111111\n811111111111119\n234234234234278\n818181911112111",
+                "357",
+                "3121910778619",
+            ),
+        ];
+        for (day, input, part1, part2) in cases {
+            let entry = find(day).unwrap();
+            assert_eq!(part1, entry.solve(Part::One, input).unwrap());
+            assert_eq!(part2, entry.solve(Part::Two, input).unwrap());
+        }
+    }
+}
@@ -0,0 +1,379 @@
+// Single entry point for every puzzle:
+//   aoc run <day> <part> [INPUT] [--lenient] [--verbose]
+//   aoc validate <day> [INPUT] [--verbose]
+//   aoc trace 1 [INPUT] [--format csv|jsonl] [--count end|click] [--verbose]
+//   aoc explain 2 <part> [INPUT] [--format table|json] [--verbose]
+//   aoc report 2 <part> [INPUT] [--format text|csv|json] [--verbose]
+// where INPUT is `--input NAME|PATH|-` or `--example`. NAME is a file in that
+// day's src/input directory, PATH is any file (it has to contain a separator
+// or start with '.'), and `-` reads stdin. Without INPUT both parts read that
+// day's real input. `--lenient` (day 1 only) skips lines that don't parse
+// instead of failing and says how many it skipped. `validate` lists every
+// line the day's parser rejects, `trace` prints every rotation of the day 1
+// dial, scoring either the end of each rotation (part 1) or every click
+// (part 2, the default). `explain`
+// lists every invalid day 2 ID with the repeated blocks it is made of,
+// `report` each day 2 range's invalid IDs with a summary of the input.
+// `explain` and `report` work on u64 IDs, so unlike `run` they refuse
+// bounds past u64::MAX (18446744073709551615).
+
+use ex_1::{Counting, Dial};
+use file_read::Source;
+use solution::{Part, Solution};
+use std::env;
+use std::error::Error;
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: aoc run <day> <part> [--input NAME|PATH|-] [--example] [--lenient] [--verbose]
+       aoc validate <day> [--input NAME|PATH|-] [--example] [--verbose]
+       aoc trace 1 [--input NAME|PATH|-] [--example] [--format csv|jsonl] [--count end|click] [--verbose]
+       aoc explain 2 <part> [--input NAME|PATH|-] [--example] [--format table|json] [--verbose]
+       aoc report 2 <part> [--input NAME|PATH|-] [--example] [--format text|csv|json] [--verbose]
+explain and report take IDs up to 18446744073709551615, run takes any";
+
+enum Command {
+    Run {
+        part: Part,
+        lenient: bool,
+    },
+    Validate,
+    Trace {
+        format: TraceFormat,
+        counting: Counting,
+    },
+    Explain {
+        part: Part,
+        format: ExplainFormat,
+    },
+    Report {
+        part: Part,
+        format: ReportFormat,
+    },
+}
+
+enum TraceFormat {
+    Csv,
+    JsonLines,
+}
+
+enum ExplainFormat {
+    Table,
+    Json,
+}
+
+enum ReportFormat {
+    Text,
+    Csv,
+    Json,
+}
+
+enum Input {
+    Default,
+    Example,
+    Given(String),
+}
+
+struct Args {
+    command: Command,
+    day: u8,
+    input: Input,
+    verbose: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let command = args.next().ok_or("missing command")?;
+    let day = args
+        .next()
+        .ok_or("missing day")?
+        .parse()
+        .map_err(|_| "day must be a number")?;
+    let mut part = || -> Result<Part, String> {
+        args.next()
+            .ok_or("missing part")?
+            .parse()
+            .map_err(|err: solution::ParsePartError| err.to_string())
+    };
+    let mut command = match command.as_str() {
+        "run" => Command::Run {
+            part: part()?,
+            lenient: false,
+        },
+        "validate" => Command::Validate,
+        "trace" => Command::Trace {
+            format: TraceFormat::Csv,
+            counting: Counting::EveryClick,
+        },
+        "explain" => Command::Explain {
+            part: part()?,
+            format: ExplainFormat::Table,
+        },
+        "report" => Command::Report {
+            part: part()?,
+            format: ReportFormat::Text,
+        },
+        other => return Err(format!("unknown command `{}`", other)),
+    };
+
+    let mut input = Input::Default;
+    let mut verbose = false;
+    while let Some(arg) = args.next() {
+        let selected = match arg.as_str() {
+            "--input" => Input::Given(args.next().ok_or("--input needs a value")?),
+            "--example" => Input::Example,
+            "-v" | "--verbose" => {
+                verbose = true;
+                continue;
+            }
+            "--format" => {
+                match &mut command {
+                    Command::Trace { format, .. } => {
+                        *format = match args.next().as_deref() {
+                            Some("csv") => TraceFormat::Csv,
+                            Some("jsonl") => TraceFormat::JsonLines,
+                            _ => return Err("--format needs csv or jsonl".to_string()),
+                        }
+                    }
+                    Command::Explain { format, .. } => {
+                        *format = match args.next().as_deref() {
+                            Some("table") => ExplainFormat::Table,
+                            Some("json") => ExplainFormat::Json,
+                            _ => return Err("--format needs table or json".to_string()),
+                        }
+                    }
+                    Command::Report { format, .. } => {
+                        *format = match args.next().as_deref() {
+                            Some("text") => ReportFormat::Text,
+                            Some("csv") => ReportFormat::Csv,
+                            Some("json") => ReportFormat::Json,
+                            _ => return Err("--format needs text, csv or json".to_string()),
+                        }
+                    }
+                    _ => {
+                        return Err(
+                            "--format only applies to trace, explain and report".to_string()
+                        );
+                    }
+                }
+                continue;
+            }
+            "--lenient" => {
+                let Command::Run { lenient, .. } = &mut command else {
+                    return Err("--lenient only applies to run".to_string());
+                };
+                *lenient = true;
+                continue;
+            }
+            "--count" => {
+                let Command::Trace { counting, .. } = &mut command else {
+                    return Err("--count only applies to trace".to_string());
+                };
+                *counting = args
+                    .next()
+                    .ok_or("--count needs end or click")?
+                    .parse()
+                    .map_err(|err: ex_1::ParseCountingError| err.to_string())?;
+                continue;
+            }
+            other => return Err(format!("unexpected argument `{}`", other)),
+        };
+        if !matches!(input, Input::Default) {
+            return Err("--input and --example can be given only once".to_string());
+        }
+        input = selected;
+    }
+    // before any input is read, so it isn't a missing file that gets reported
+    if matches!(command, Command::Run { lenient: true, .. }) && day != ex_1::Day1::DAY {
+        return Err("--lenient only applies to day 1".to_string());
+    }
+    Ok(Args {
+        command,
+        day,
+        input,
+        verbose,
+    })
+}
+
+fn run(args: &Args) -> Result<ExitCode, Box<dyn Error>> {
+    let day = aoc::find(args.day).ok_or_else(|| format!("no solver for day {}", args.day))?;
+    let spec = match &args.input {
+        Input::Default => day.input,
+        Input::Example => day.example,
+        Input::Given(spec) => spec,
+    };
+    let source = Source::resolve(day.root, spec);
+    if args.verbose {
+        file_read::set_tracing(true);
+        eprintln!("input: {}", source);
+    }
+    let input = source.read_to_string()?;
+
+    match args.command {
+        Command::Run {
+            part,
+            lenient: true,
+        } => {
+            let (answer, skipped) = ex_1::Day1::solve_lenient(part, &input);
+            if args.verbose {
+                for invalid in &skipped {
+                    eprintln!(
+                        "skipped {}",
+                        file_read::Error::from(invalid.clone()).with_path(source.path())
+                    );
+                }
+            }
+            eprintln!("{}: skipped {} invalid lines", source, skipped.len());
+            println!("{}", answer);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Run { part, .. } => {
+            let answer = day
+                .solve(part, &input)
+                .map_err(|err| err.with_path(source.path()))?;
+            println!("{}", answer);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Validate => {
+            let errors = day.validate(&input);
+            let count = errors.len();
+            for err in errors {
+                println!("{}", err.with_path(source.path()));
+            }
+            if count == 0 {
+                println!("{}: ok", source);
+                Ok(ExitCode::SUCCESS)
+            } else {
+                println!("{}: {} invalid lines", source, count);
+                Ok(ExitCode::FAILURE)
+            }
+        }
+        Command::Trace {
+            ref format,
+            counting,
+        } => {
+            if day.day != ex_1::Day1::DAY {
+                return Err(format!("day {} has no trace", day.day).into());
+            }
+            let operations =
+                ex_1::Day1::parse(&input).map_err(|err| err.with_path(source.path()))?;
+            match print_trace(&operations, format, counting) {
+                // the reader (e.g. `head`) has seen enough
+                Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(ExitCode::SUCCESS),
+                result => result.map(|()| ExitCode::SUCCESS).map_err(Into::into),
+            }
+        }
+        Command::Explain { part, ref format } => {
+            if day.day != ex_2::Day2::DAY {
+                return Err(format!("day {} has no explain", day.day).into());
+            }
+            // explanations are per ID, so only u64 IDs, and the error for a
+            // bigger one says so; the ranges as given, so each ID names a
+            // range from the input
+            let ranges =
+                ex_2::parse_range_list(&input).map_err(|err| err.with_path(source.path()))?;
+            match print_explanations(&ranges, day2_policy(part), format) {
+                Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(ExitCode::SUCCESS),
+                result => result.map(|()| ExitCode::SUCCESS).map_err(Into::into),
+            }
+        }
+        Command::Report { part, ref format } => {
+            if day.day != ex_2::Day2::DAY {
+                return Err(format!("day {} has no report", day.day).into());
+            }
+            // the ranges as given, not merged like Day2::parse does; u64
+            // ones only, like explain
+            let ranges =
+                ex_2::parse_range_list(&input).map_err(|err| err.with_path(source.path()))?;
+            let report = ex_2::Report::new(ranges, day2_policy(part));
+            match print_report(&report, format) {
+                Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(ExitCode::SUCCESS),
+                result => result.map(|()| ExitCode::SUCCESS).map_err(Into::into),
+            }
+        }
+    }
+}
+
+fn print_trace(
+    operations: &[ex_1::Operation],
+    format: &TraceFormat,
+    counting: Counting,
+) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    if let TraceFormat::Csv = format {
+        writeln!(out, "{}", ex_1::Step::CSV_HEADER)?;
+    }
+    for step in Dial::default().trace(operations, counting) {
+        match format {
+            TraceFormat::Csv => writeln!(out, "{}", step.to_csv())?,
+            TraceFormat::JsonLines => writeln!(out, "{}", step.to_json())?,
+        }
+    }
+    out.flush()
+}
+
+fn day2_policy(part: Part) -> ex_2::IdPolicy {
+    match part {
+        Part::One => ex_2::IdPolicy::TWICE,
+        Part::Two => ex_2::IdPolicy::AT_LEAST_TWICE,
+    }
+}
+
+// a table, or one JSON array with an object per line
+fn print_explanations(
+    ranges: &[ex_2::IdRange],
+    policy: ex_2::IdPolicy,
+    format: &ExplainFormat,
+) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    match format {
+        ExplainFormat::Table => writeln!(out, "{}", ex_2::Explanation::TABLE_HEADER)?,
+        ExplainFormat::Json => write!(out, "[")?,
+    }
+    for (i, explanation) in ex_2::explain(ranges, policy).enumerate() {
+        match format {
+            ExplainFormat::Table => writeln!(out, "{}", explanation.to_row())?,
+            ExplainFormat::Json => {
+                let separator = if i == 0 { "" } else { "," };
+                write!(out, "{}\n{}", separator, explanation.to_json())?
+            }
+        }
+    }
+    if let ExplainFormat::Json = format {
+        writeln!(out, "\n]")?;
+    }
+    out.flush()
+}
+
+fn print_report(report: &ex_2::Report, format: &ReportFormat) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    match format {
+        ReportFormat::Text => write!(out, "{}", report.to_text())?,
+        ReportFormat::Csv => write!(out, "{}", report.to_csv())?,
+        ReportFormat::Json => writeln!(out, "{}", report.to_json())?,
+    }
+    out.flush()
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&args) {
+        Ok(code) => code,
+        Err(err) => {
+            match args.command {
+                Command::Run { part, .. }
+                | Command::Explain { part, .. }
+                | Command::Report { part, .. } => {
+                    eprintln!("day {} part {}: {}", args.day, part, err)
+                }
+                Command::Validate | Command::Trace { .. } => eprintln!("day {}: {}", args.day, err),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
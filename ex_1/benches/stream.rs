@@ -0,0 +1,111 @@
+// Throughput of `Dial::stream` on a synthetic rotation file, 1 GiB unless
+// STREAM_BENCH_BYTES says otherwise. The file is never held in memory: a
+// 1 MiB block of random rotations is handed out again and again, so what is
+// measured is the parser and the dial, and memory use stays flat.
+//
+//   cargo bench -p ex_1 --bench stream
+//   STREAM_BENCH_BYTES=100000000 cargo bench -p ex_1 --bench stream
+//
+// For comparison the same bytes also go through BufRead::lines, which
+// allocates a String per line, on a tenth of the size.
+
+use ex_1::{Counting, Dial, Operation};
+use std::env;
+use std::io::{self, BufRead, BufReader, Read};
+use std::time::Instant;
+
+const BLOCK: usize = 1 << 20;
+
+// `total` bytes made of whole copies of `block`
+struct Synthetic {
+    block: Vec<u8>,
+    offset: usize,
+    remaining: u64,
+}
+
+impl Synthetic {
+    fn new(block: &[u8], total: u64) -> Synthetic {
+        let copies = (total / block.len() as u64).max(1);
+        Synthetic {
+            block: block.to_vec(),
+            offset: 0,
+            remaining: copies * block.len() as u64,
+        }
+    }
+
+    fn len(block: &[u8], total: u64) -> u64 {
+        Synthetic::new(block, total).remaining
+    }
+}
+
+impl Read for Synthetic {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = (self.block.len() - self.offset) as u64;
+        let len = buf.len().min(available.min(self.remaining) as usize);
+        buf[..len].copy_from_slice(&self.block[self.offset..self.offset + len]);
+        self.offset = (self.offset + len) % self.block.len();
+        self.remaining -= len as u64;
+        Ok(len)
+    }
+}
+
+// random rotations, one per line, up to about BLOCK bytes
+fn block() -> Vec<u8> {
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut block = Vec::with_capacity(BLOCK);
+    while block.len() < BLOCK - 8 {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let dist = (state >> 32) as u16 % 1000;
+        let direction = if state & 1 == 0 { 'L' } else { 'R' };
+        block.extend_from_slice(format!("{}{}\n", direction, dist).as_bytes());
+    }
+    block
+}
+
+fn report(name: &str, bytes: u64, lines: u64, started: Instant, password: u64) {
+    let seconds = started.elapsed().as_secs_f64();
+    println!(
+        "{:>6}: {:>6} MiB in {:6.2}s, {:8.1} MiB/s, {:6.1} M lines/s (password {})",
+        name,
+        bytes >> 20,
+        seconds,
+        bytes as f64 / seconds / f64::from(1 << 20),
+        lines as f64 / seconds / 1e6,
+        password
+    );
+}
+
+fn main() {
+    let total = env::var("STREAM_BENCH_BYTES")
+        .ok()
+        .and_then(|bytes| bytes.parse().ok())
+        .unwrap_or(1 << 30);
+    let block = block();
+    let lines_per_block = block.iter().filter(|&&b| b == b'\n').count() as u64;
+    let dial = Dial::default();
+
+    let bytes = Synthetic::len(&block, total);
+    let started = Instant::now();
+    let execution = dial
+        .stream(Synthetic::new(&block, total), Counting::EveryClick)
+        .expect("synthetic input is valid");
+    let lines = bytes / block.len() as u64 * lines_per_block;
+    report("stream", bytes, lines, started, execution.password);
+
+    let total = total / 10;
+    let bytes = Synthetic::len(&block, total);
+    let started = Instant::now();
+    let mut position = dial.start();
+    let mut password: u64 = 0;
+    for line in BufReader::new(Synthetic::new(&block, total)).lines() {
+        let operation: Operation = line.unwrap().parse().unwrap();
+        let (end, hits) = dial.rotate_counting(position, &operation);
+        position = end;
+        password += u64::from(hits);
+    }
+    let lines = bytes / block.len() as u64 * lines_per_block;
+    report("lines", bytes, lines, started, password);
+}
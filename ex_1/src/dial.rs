@@ -0,0 +1,298 @@
+use crate::Operation;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// A dial with positions 0..size that starts at `start`; the password counts
+// how often the dial is at `target`. The puzzle's dial is the default one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    size: u32,
+    start: u32,
+    target: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialError {
+    ZeroSize,
+    StartOutOfRange,
+    TargetOutOfRange,
+    PositionOutOfRange(u32),
+    // a script whose repeats run more steps than `Dial::MAX_SCRIPT_STEPS`
+    ScriptTooLong,
+}
+
+impl fmt::Display for DialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialError::ZeroSize => write!(f, "dial needs at least one position"),
+            DialError::StartOutOfRange => write!(f, "start is not a position on the dial"),
+            DialError::TargetOutOfRange => write!(f, "target is not a position on the dial"),
+            DialError::PositionOutOfRange(position) => {
+                write!(f, "{} is not a position on the dial", position)
+            }
+            DialError::ScriptTooLong => write!(
+                f,
+                "script runs more than {} steps once repeats are expanded",
+                Dial::MAX_SCRIPT_STEPS
+            ),
+        }
+    }
+}
+
+impl Error for DialError {}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Dial {
+            size: 100,
+            start: 50,
+            target: 0,
+        }
+    }
+}
+
+impl Dial {
+    pub fn new(size: u32, start: u32, target: u32) -> Result<Dial, DialError> {
+        if size == 0 {
+            Err(DialError::ZeroSize)
+        } else if start >= size {
+            Err(DialError::StartOutOfRange)
+        } else if target >= size {
+            Err(DialError::TargetOutOfRange)
+        } else {
+            Ok(Dial {
+                size,
+                start,
+                target,
+            })
+        }
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    pub fn target(&self) -> u32 {
+        self.target
+    }
+
+    // The one place that does dial arithmetic; everything else reads the
+    // events off the returned Rotation. `position` isn't checked: one past
+    // the dial is taken modulo its size, so 250 on the puzzle's dial is 50.
+    // The arithmetic is in i64, a dial can have up to u32::MAX positions.
+    pub fn simulate(&self, position: u32, operation: &Operation) -> Rotation {
+        let size = i64::from(self.size);
+        // measure from the target so that it becomes position 0
+        let relative = (i64::from(position) - i64::from(self.target)).rem_euclid(size);
+        let distance: i64 = match *operation {
+            Operation::L(dist) => -i64::from(dist),
+            Operation::R(dist) => i64::from(dist),
+        };
+        let result = relative + distance;
+        let mut hits = (result.unsigned_abs() / self.size as u64) as u32;
+
+        if result <= 0 && relative != 0 {
+            hits += 1;
+        }
+
+        // rem_euclid keeps the end in 0..size even for a negative result,
+        // -7.rem_euclid(100) is 93 where -7 % 100 is -7
+        let end = (result + i64::from(self.target)).rem_euclid(size) as u32;
+        let landed = end == self.target;
+        // `hits` includes the last click, unless the dial did not move at all
+        let last_click = u32::from(landed && distance != 0);
+        Rotation {
+            end,
+            landed,
+            passed: hits - last_click,
+            moved: distance != 0,
+        }
+    }
+
+    // where the dial points after `operation`; whole turns don't matter,
+    // and neither do they for `position` (see `simulate`)
+    pub fn rotate(&self, position: u32, operation: &Operation) -> u32 {
+        self.simulate(position, operation).end
+    }
+
+    // where the dial points after `operation` and how many clicks on the way,
+    // the last one included, landed on the target
+    pub fn rotate_counting(&self, position: u32, operation: &Operation) -> (u32, u32) {
+        let rotation = self.simulate(position, operation);
+        (rotation.end, Counting::EveryClick.count(&rotation))
+    }
+
+    pub fn password(&self, operations: &[Operation], counting: Counting) -> u32 {
+        let mut password = 0;
+        let mut position = self.start;
+        for operation in operations {
+            let rotation = self.simulate(position, operation);
+            password += counting.count(&rotation);
+            position = rotation.end;
+        }
+        password
+    }
+
+    // part 1: rotations that end on the target
+    pub fn count_landings(&self, operations: &[Operation]) -> u32 {
+        self.password(operations, Counting::EndOfRotation)
+    }
+
+    // part 2: every click that points at the target
+    pub fn count_passes(&self, operations: &[Operation]) -> u32 {
+        self.password(operations, Counting::EveryClick)
+    }
+}
+
+// What happened during one rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation {
+    pub end: u32,
+    // the dial is left pointing at the target
+    pub landed: bool,
+    // clicks on the target before the last one, i.e. passing through it
+    pub passed: u32,
+    // false for a zero distance rotation, which makes no clicks at all
+    pub moved: bool,
+}
+
+// Which events of a rotation add to the password.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counting {
+    // part 1: only where the dial is left
+    EndOfRotation,
+    // part 2: every click that points at the target
+    EveryClick,
+}
+
+impl Counting {
+    pub fn count(&self, rotation: &Rotation) -> u32 {
+        match self {
+            Counting::EndOfRotation => u32::from(rotation.landed),
+            Counting::EveryClick => rotation.passed + u32::from(rotation.landed && rotation.moved),
+        }
+    }
+}
+
+impl fmt::Display for Counting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Counting::EndOfRotation => write!(f, "end"),
+            Counting::EveryClick => write!(f, "click"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseCountingError;
+
+impl fmt::Display for ParseCountingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "counting must be `end` or `click`")
+    }
+}
+
+impl Error for ParseCountingError {}
+
+impl FromStr for Counting {
+    type Err = ParseCountingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "end" => Ok(Counting::EndOfRotation),
+            "click" => Ok(Counting::EveryClick),
+            _ => Err(ParseCountingError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_test() {
+        assert_eq!(Ok(Dial::default()), Dial::new(100, 50, 0));
+        assert_eq!(Err(DialError::ZeroSize), Dial::new(0, 0, 0));
+        assert_eq!(Err(DialError::StartOutOfRange), Dial::new(10, 10, 0));
+        assert_eq!(Err(DialError::TargetOutOfRange), Dial::new(10, 0, 12));
+    }
+
+    #[test]
+    fn huge_dial_test() {
+        let dial = Dial::new(u32::MAX, 5, 0).unwrap();
+        assert_eq!(8, dial.rotate(5, &Operation::R(3)));
+        assert_eq!((u32::MAX - 1, 1), dial.rotate_counting(5, &Operation::L(6)));
+        let dial = Dial::new(u32::MAX, 0, u32::MAX - 1).unwrap();
+        assert_eq!((1, 1), dial.rotate_counting(u32::MAX - 2, &Operation::R(3)));
+    }
+
+    #[test]
+    fn unchecked_position_test() {
+        // positions past the dial wrap around like whole turns
+        let dial = Dial::default();
+        assert_eq!(53, dial.rotate(250, &Operation::R(3)));
+        assert_eq!(
+            dial.rotate_counting(50, &Operation::L(60)),
+            dial.rotate_counting(250, &Operation::L(60))
+        );
+    }
+
+    #[test]
+    fn small_dial_test() {
+        let dial = Dial::new(10, 5, 0).unwrap();
+        assert_eq!(2, dial.rotate(5, &Operation::R(7)));
+        assert_eq!(8, dial.rotate(5, &Operation::L(27)));
+        assert_eq!((2, 1), dial.rotate_counting(5, &Operation::R(7)));
+        assert_eq!((8, 3), dial.rotate_counting(5, &Operation::L(27)));
+        assert_eq!((0, 1), dial.rotate_counting(5, &Operation::L(5)));
+        assert_eq!((0, 2), dial.rotate_counting(0, &Operation::R(20)));
+    }
+
+    #[test]
+    fn target_test() {
+        // 12 positions like a clock, counting how often we see 3
+        let dial = Dial::new(12, 0, 3).unwrap();
+        assert_eq!((2, 1), dial.rotate_counting(0, &Operation::R(14)));
+        assert_eq!((3, 1), dial.rotate_counting(5, &Operation::L(2)));
+        assert_eq!((3, 2), dial.rotate_counting(3, &Operation::L(24)));
+        assert_eq!((4, 0), dial.rotate_counting(3, &Operation::R(1)));
+
+        let operations = [Operation::R(3), Operation::R(12), Operation::L(1)];
+        assert_eq!(2, dial.count_landings(&operations));
+        assert_eq!(2, dial.count_passes(&operations));
+    }
+
+    #[test]
+    fn single_position_dial_test() {
+        let dial = Dial::new(1, 0, 0).unwrap();
+        assert_eq!((0, 7), dial.rotate_counting(0, &Operation::L(7)));
+        assert_eq!(1, dial.count_landings(&[Operation::R(3)]));
+    }
+
+    #[test]
+    fn password_policies_test() {
+        let operations = [
+            Operation::L(68),
+            Operation::L(30),
+            Operation::R(48),
+            Operation::L(5),
+            Operation::R(60),
+            Operation::L(55),
+            Operation::L(1),
+            Operation::L(99),
+            Operation::R(14),
+            Operation::L(82),
+        ];
+        let dial = Dial::default();
+        assert_eq!(3, dial.password(&operations, Counting::EndOfRotation));
+        assert_eq!(6, dial.password(&operations, Counting::EveryClick));
+        assert_eq!(Ok(Counting::EveryClick), "click".parse());
+        assert!("both".parse::<Counting>().is_err());
+    }
+}
@@ -0,0 +1,93 @@
+// Random dials and operations for the differential tests, in the spirit of
+// proptest strategies but without the dependency. Seeds are fixed, so a
+// failing case number is enough to reproduce it.
+
+use crate::{Dial, Operation};
+
+pub struct Gen {
+    state: u64,
+}
+
+impl Gen {
+    pub fn new(seed: u64) -> Gen {
+        // xorshift never leaves 0
+        Gen { state: seed.max(1) }
+    }
+
+    // xorshift64
+    pub fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    // uniform in 0..n
+    pub fn below(&mut self, n: u32) -> u32 {
+        (self.next() % u64::from(n)) as u32
+    }
+
+    // skewed towards the distances where off-by-one mistakes live
+    pub fn distance(&mut self) -> u16 {
+        match self.below(8) {
+            // as far as a u16 goes
+            0 => u16::MAX - self.below(200) as u16,
+            // around whole turns of the puzzle dial
+            1 => ((self.below(600) * 100) as u16 + self.below(3) as u16).saturating_sub(1),
+            2 => self.below(3) as u16,
+            3 => self.next() as u16,
+            _ => self.below(400) as u16,
+        }
+    }
+
+    pub fn operation(&mut self) -> Operation {
+        let dist = self.distance();
+        if self.below(2) == 0 {
+            Operation::L(dist)
+        } else {
+            Operation::R(dist)
+        }
+    }
+
+    pub fn operations(&mut self, max_len: usize) -> Vec<Operation> {
+        let len = self.below(max_len as u32 + 1);
+        (0..len).map(|_| self.operation()).collect()
+    }
+
+    // mostly small odd sizes, sometimes the puzzle's own dial
+    pub fn dial(&mut self) -> Dial {
+        let size = if self.below(4) == 0 {
+            100
+        } else {
+            self.below(120) + 1
+        };
+        let start = self.below(size);
+        let target = self.below(size);
+        Dial::new(size, start, target).expect("start and target are below size")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_test() {
+        // any seed, not just the ones the other tests happen to use
+        let mut seen = std::collections::HashSet::new();
+        for seed in 0..2000 {
+            let mut generate = Gen::new(seed);
+            for _ in 0..50 {
+                seen.insert(generate.distance());
+            }
+        }
+        // the edges the skew is there for: no clicks, a whole turn and one
+        // either side of it, the first turn minus one where the old code
+        // underflowed, the last turn, and as far as a u16 goes
+        for edge in [0, 1, 99, 100, 101, 59899, 59900, 59901, u16::MAX] {
+            assert!(seen.contains(&edge), "{} never generated", edge);
+        }
+        // and plenty of everything else
+        assert!(seen.len() > 10_000);
+    }
+}
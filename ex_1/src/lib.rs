@@ -4,132 +4,79 @@
 // - Starting at 11, apply R8 to land on 19, then L19 to land on 0.
 // - Starting at 5, apply L10 to land on 95, then R5 to land on 0.
 
-use std::fmt;
-use std::str::FromStr;
-
-#[derive(Debug)]
-enum Operation {
-    L(u16),
-    R(u16),
-}
-
-impl fmt::Display for Operation {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Operation::L(dist) => write!(f, "L{}", dist),
-            Operation::R(dist) => write!(f, "R{}", dist),
-        }
+mod dial;
+#[cfg(test)]
+mod generate;
+mod operation;
+pub mod oracle;
+mod parallel;
+mod script;
+mod search;
+mod stream;
+mod trace;
+
+pub use dial::{Counting, Dial, DialError, ParseCountingError, Rotation};
+pub use operation::{InvalidLine, Operation, ParseOperationError, Validation};
+pub use parallel::Transform;
+pub use script::{Execution, Statement, parse_script};
+pub use search::{Goal, Search};
+pub use trace::{Step, Trace};
+
+use solution::{Part, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const ROOT: &'static str = env!("CARGO_MANIFEST_DIR");
+    const INPUT: &'static str = "password_input_final";
+    const EXAMPLE: &'static str = "example";
+
+    type Input = Vec<Operation>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    // strict mode: the first bad line fails the whole input
+    fn parse(input: &str) -> file_read::Result<Self::Input> {
+        Validation::scan(input)
+            .strict()
+            .map_err(|invalid| invalid.into_iter().next().expect("rejected lines").into())
     }
-}
-
-#[derive(Debug)]
-struct ParseOperationError;
 
-impl FromStr for Operation {
-    type Err = ParseOperationError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (dir, dist) = s.split_at(1);
-        let dist: u16 = dist.parse().map_err(|_| ParseOperationError)?;
-        match dir {
-            "R" => Ok(Operation::R(dist)),
-            "L" => Ok(Operation::L(dist)),
-            _ => Err(ParseOperationError),
-        }
+    fn validate(input: &str) -> Vec<file_read::Error> {
+        let invalid = Validation::scan(input).invalid;
+        invalid.into_iter().map(file_read::Error::from).collect()
     }
-}
 
-fn main_1() -> std::io::Result<()> {
-    let mut password = 0;
-    let mut starting_point = 50;
-    let reader = file_read::read_to_buffer_lines("password_input_final")?;
-    for line in reader {
-        let line = line?;
-        let operation: Operation = line.parse().expect("invalid operation");
-
-        let result = calculate_part1(starting_point, &operation);
-        println!("{} {}: {}", starting_point, operation, result);
-        starting_point = result;
-        if starting_point == 0 {
-            password += 1
-        }
+    fn part1(operations: &Self::Input) -> u32 {
+        Dial::default().count_landings(operations)
     }
 
-    println!("password: {}", password);
-    Ok(())
+    fn part2(operations: &Self::Input) -> u32 {
+        Dial::default().count_passes(operations)
+    }
 }
 
-fn main() -> std::io::Result<()> {
-    let mut password = 0;
-    let mut starting_point = 50;
-    let reader = file_read::read_to_buffer_lines("password_input_final")?;
-    for line in reader {
-        let line = line?;
-        let operation: Operation = line.parse().expect("invalid operation");
-
-        let result = calculate_part2(starting_point, &operation);
-        println!(
-            "{} {}: new starting point {} with password {}",
-            starting_point, operation, result.0, result.1
-        );
-        starting_point = result.0;
-        password += result.1
+impl Day1 {
+    // lenient mode: the answer from the lines that parse, with the lines
+    // that didn't, so the caller can say how many were skipped
+    pub fn solve_lenient(part: Part, input: &str) -> (u32, Vec<InvalidLine>) {
+        let validation = Validation::scan(input);
+        let answer = match part {
+            Part::One => Day1::part1(&validation.operations),
+            Part::Two => Day1::part2(&validation.operations),
+        };
+        (answer, validation.invalid)
     }
-
-    println!("password: {}", password);
-    Ok(())
 }
 
-fn calculate_part1(starting_point: u8, operation: &Operation) -> u8 {
-    let result = match *operation {
-        Operation::L(dist) => {
-            let mut res: i32 = starting_point as i32 - dist as i32 % 100;
-            if res < 0 {
-                res += 100;
-            }
-            res
-        }
-        Operation::R(dist) => {
-            let mut res: i32 = starting_point as i32 + dist as i32 % 100;
-            if res > 99 {
-                res -= 100;
-            }
-            res
-        }
-    };
-    result as u8
+// the puzzle's 100 position dial, kept for the step by step tests below
+pub fn calculate_part1(starting_point: u8, operation: &Operation) -> u8 {
+    Dial::default().rotate(u32::from(starting_point), operation) as u8
 }
 
-fn calculate_part2(starting_point: u8, operation: &Operation) -> (u8, u32) {
-    let distance: i32 = match *operation {
-        Operation::L(dist) => -(i32::from(dist)),
-        Operation::R(dist) => i32::from(dist),
-    };
-    let result = i32::from(starting_point) + distance;
-    let mut zeros: u32 = result.unsigned_abs().div_euclid(100);
-
-    if result <= 0 && starting_point != 0 {
-        zeros += 1;
-    }
-
-    // let mut position = result % 100;
-    // if position < 0 {
-    //     position += 100
-    // }
-    // rem_euclid always returns positive reminder, no need for +=100 fix;
-    // -7.rem_euclid(100) = 93, since euclidean remainder keeps results in [0,100), -7-100*(-1)=93
-
-    // learnt something new:
-    // -7.rem_eu(3)=-7-3*(-3)=2? because -7/3=-2.xx, floor -3?
-    // Answer:
-    // - Real division: -7 / 3 ≈ -2.333.
-    // - Euclidean division uses the floor (toward −∞), so floor(-2.333) = -3.
-    // - Plug that into dividend = divisor * quotient + remainder:
-    //     -7 = 3 * (-3) + remainder → remainder = -7 - (-9) = 2.
-
-    // Since 2 is in [0, 3), that’s the result.
-
-    let position = result.rem_euclid(100);
+pub fn calculate_part2(starting_point: u8, operation: &Operation) -> (u8, u32) {
+    let (position, zeros) = Dial::default().rotate_counting(u32::from(starting_point), operation);
     (position as u8, zeros)
 }
 
@@ -137,6 +84,27 @@ fn calculate_part2(starting_point: u8, operation: &Operation) -> (u8, u32) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn solve_lenient_test() {
+        // the example with two bad lines mixed in
+        let input = "L68\nL30\nF27\nR48\nL5\nR60\nL55\nL-12\nL1\nL99\nR14\nL82";
+        let (answer, skipped) = Day1::solve_lenient(Part::One, input);
+        assert_eq!(3, answer);
+        assert_eq!(
+            vec![3, 8],
+            skipped
+                .iter()
+                .map(|invalid| invalid.line)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(6, Day1::solve_lenient(Part::Two, input).0);
+        // strict parsing stops at the first of them
+        assert_eq!(
+            "3: unknown direction `F`, expected L or R in `F27`",
+            Day1::parse(input).unwrap_err().to_string()
+        );
+    }
+
     #[test]
     fn calculate_part1_works() {
         assert_eq!(calculate_part1(50, &Operation::L(5)), 45);
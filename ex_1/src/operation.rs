@@ -0,0 +1,192 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    L(u16),
+    R(u16),
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::L(dist) => write!(f, "L{}", dist),
+            Operation::R(dist) => write!(f, "R{}", dist),
+        }
+    }
+}
+
+// why a line is not an operation, e.g. `F27` or `L-12` in password_input_invalid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseOperationError {
+    Empty,
+    TrailingWhitespace,
+    UnknownDirection(char),
+    MissingDistance,
+    NegativeDistance,
+    // more clicks than fit in u16
+    Overflow,
+    InvalidDistance,
+}
+
+impl fmt::Display for ParseOperationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseOperationError::Empty => write!(f, "empty line"),
+            ParseOperationError::TrailingWhitespace => write!(f, "trailing whitespace"),
+            ParseOperationError::UnknownDirection(dir) => {
+                write!(f, "unknown direction `{}`, expected L or R", dir)
+            }
+            ParseOperationError::MissingDistance => write!(f, "missing distance"),
+            ParseOperationError::NegativeDistance => write!(f, "negative distance"),
+            ParseOperationError::Overflow => {
+                write!(f, "distance does not fit in {} clicks", u16::MAX)
+            }
+            ParseOperationError::InvalidDistance => write!(f, "distance is not a number"),
+        }
+    }
+}
+
+impl Error for ParseOperationError {}
+
+impl FromStr for Operation {
+    type Err = ParseOperationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseOperationError::Empty);
+        }
+        if s.trim_end() != s {
+            return Err(ParseOperationError::TrailingWhitespace);
+        }
+        let mut chars = s.chars();
+        let dir = chars.next().ok_or(ParseOperationError::Empty)?;
+        let dist = chars.as_str();
+
+        if dir != 'L' && dir != 'R' {
+            return Err(ParseOperationError::UnknownDirection(dir));
+        }
+        if dist.is_empty() {
+            return Err(ParseOperationError::MissingDistance);
+        }
+        let digits = dist.strip_prefix('-').unwrap_or(dist);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseOperationError::InvalidDistance);
+        }
+        if digits.len() != dist.len() {
+            return Err(ParseOperationError::NegativeDistance);
+        }
+        // only digits are left, so the parse can fail on size alone
+        let dist: u16 = dist.parse().map_err(|_| ParseOperationError::Overflow)?;
+        match dir {
+            'R' => Ok(Operation::R(dist)),
+            _ => Ok(Operation::L(dist)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidLine {
+    // 1-based
+    pub line: usize,
+    pub content: String,
+    pub reason: ParseOperationError,
+}
+
+impl From<InvalidLine> for file_read::Error {
+    fn from(invalid: InvalidLine) -> Self {
+        file_read::Error::parse(invalid.line, &invalid.content, invalid.reason)
+    }
+}
+
+// Result of checking every line instead of stopping at the first bad one.
+#[derive(Debug, Default)]
+pub struct Validation {
+    pub operations: Vec<Operation>,
+    pub invalid: Vec<InvalidLine>,
+}
+
+impl Validation {
+    pub fn scan(input: &str) -> Validation {
+        let mut validation = Validation::default();
+        for (index, line) in input.lines().enumerate() {
+            match line.parse() {
+                Ok(operation) => validation.operations.push(operation),
+                Err(reason) => validation.invalid.push(InvalidLine {
+                    line: index + 1,
+                    content: line.to_string(),
+                    reason,
+                }),
+            }
+        }
+        validation
+    }
+
+    // strict mode: the operations only if no line was rejected
+    pub fn strict(self) -> Result<Vec<Operation>, Vec<InvalidLine>> {
+        if self.invalid.is_empty() {
+            Ok(self.operations)
+        } else {
+            Err(self.invalid)
+        }
+    }
+
+    // lenient mode: bad lines are dropped, `skipped` says how many
+    pub fn skipped(&self) -> usize {
+        self.invalid.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_operation_test() {
+        assert_eq!(Ok(Operation::R(49)), "R49".parse());
+        assert_eq!(Ok(Operation::L(0)), "L0".parse());
+        assert_eq!(Ok(Operation::L(u16::MAX)), "L65535".parse());
+    }
+
+    #[test]
+    fn parse_operation_errors_test() {
+        let cases = [
+            ("", ParseOperationError::Empty),
+            ("R5 ", ParseOperationError::TrailingWhitespace),
+            ("F27", ParseOperationError::UnknownDirection('F')),
+            ("é2", ParseOperationError::UnknownDirection('é')),
+            ("R", ParseOperationError::MissingDistance),
+            ("L-12", ParseOperationError::NegativeDistance),
+            ("R65536", ParseOperationError::Overflow),
+            ("R1x", ParseOperationError::InvalidDistance),
+            ("R+5", ParseOperationError::InvalidDistance),
+            ("L-", ParseOperationError::InvalidDistance),
+        ];
+        for (line, reason) in cases {
+            assert_eq!(Err(reason), line.parse::<Operation>(), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn validation_test() {
+        let input = "R49\nF27\nL-12\nR50\n\nR1 ";
+        let validation = Validation::scan(input);
+        assert_eq!(
+            vec![Operation::R(49), Operation::R(50)],
+            validation.operations
+        );
+        assert_eq!(4, validation.skipped());
+
+        let invalid = validation.strict().unwrap_err();
+        let lines: Vec<(usize, &str)> = invalid
+            .iter()
+            .map(|invalid| (invalid.line, invalid.content.as_str()))
+            .collect();
+        assert_eq!(vec![(2, "F27"), (3, "L-12"), (5, ""), (6, "R1 ")], lines);
+        assert_eq!(
+            ParseOperationError::UnknownDirection('F'),
+            invalid[0].reason
+        );
+    }
+}
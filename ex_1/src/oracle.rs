@@ -0,0 +1,125 @@
+// Reference dial that turns one click at a time and looks at every click.
+// Far too slow for real inputs, but obviously right, so the closed form in
+// `Dial::simulate` is tested against it.
+
+use crate::{Counting, Dial, Operation, Rotation};
+
+pub fn rotate(dial: &Dial, position: u32, operation: &Operation) -> Rotation {
+    let (step, dist) = match *operation {
+        Operation::L(dist) => (dial.size() - 1, dist),
+        Operation::R(dist) => (1, dist),
+    };
+    let mut position = position;
+    let mut clicks_on_target = 0;
+    for click in 1..=dist {
+        position = (position + step) % dial.size();
+        if position == dial.target() && click < dist {
+            clicks_on_target += 1;
+        }
+    }
+    Rotation {
+        end: position,
+        landed: position == dial.target(),
+        passed: clicks_on_target,
+        moved: dist > 0,
+    }
+}
+
+pub fn password(dial: &Dial, operations: &[Operation], counting: Counting) -> u32 {
+    let mut password = 0;
+    let mut position = dial.start();
+    for operation in operations {
+        let rotation = rotate(dial, position, operation);
+        password += counting.count(&rotation);
+        position = rotation.end;
+    }
+    password
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_part2;
+    use crate::generate::Gen;
+
+    #[test]
+    fn oracle_example_test() {
+        let dial = Dial::default();
+        assert_eq!(
+            Rotation {
+                end: 82,
+                landed: false,
+                passed: 1,
+                moved: true,
+            },
+            rotate(&dial, 50, &Operation::L(68))
+        );
+        assert_eq!(
+            Rotation {
+                end: 0,
+                landed: true,
+                passed: 0,
+                moved: true,
+            },
+            rotate(&dial, 52, &Operation::R(48))
+        );
+    }
+
+    #[test]
+    fn calculate_part2_matches_oracle_test() {
+        let dial = Dial::default();
+        let mut random = Gen::new(0x9e37_79b9_7f4a_7c15);
+        for case in 0..1000 {
+            let start = random.below(100);
+            let operation = random.operation();
+            let expected = rotate(&dial, start, &operation);
+            let expected = (expected.end as u8, Counting::EveryClick.count(&expected));
+            assert_eq!(
+                expected,
+                calculate_part2(start as u8, &operation),
+                "case {}: {} from {}",
+                case,
+                operation,
+                start
+            );
+        }
+    }
+
+    #[test]
+    fn simulate_matches_oracle_test() {
+        let mut random = Gen::new(0x2545_f491_4f6c_dd1d);
+        for case in 0..1000 {
+            let dial = random.dial();
+            let position = random.below(dial.size());
+            let operation = random.operation();
+            assert_eq!(
+                rotate(&dial, position, &operation),
+                dial.simulate(position, &operation),
+                "case {}: {:?} {} from {}",
+                case,
+                dial,
+                operation,
+                position
+            );
+        }
+    }
+
+    #[test]
+    fn password_matches_oracle_test() {
+        let mut random = Gen::new(0x1234_5678_9abc_def1);
+        for case in 0..100 {
+            let dial = random.dial();
+            let operations = random.operations(20);
+            for counting in [Counting::EndOfRotation, Counting::EveryClick] {
+                assert_eq!(
+                    password(&dial, &operations, counting),
+                    dial.password(&operations, counting),
+                    "case {}: {:?} {:?}",
+                    case,
+                    dial,
+                    counting
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+// Evaluating the dial on many threads. A rotation moves every position by
+// the same amount, so a whole run of operations is summed up by
+//   - its net offset: where the dial ends is entry + offset (mod size), and
+//   - a table of how much it scores for each entry position.
+// Two of these compose into one, and composing is associative, so chunks can
+// be summarised independently and then combined in order.
+//
+// The table has one entry per dial position, which is fine for dials the
+// size of the puzzle's but not for ones with millions of positions, so
+// `par_execute` runs those one operation at a time instead.
+
+use crate::{Counting, Dial, Execution, Operation};
+use rayon::prelude::*;
+
+// 512 KB of table per chunk
+const MAX_PARALLEL_SIZE: u32 = 1 << 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transform {
+    offset: u32,
+    // score of the run when it starts at that position
+    counts: Vec<u64>,
+}
+
+impl Transform {
+    // no operations at all
+    pub fn identity(dial: &Dial) -> Transform {
+        Transform {
+            offset: 0,
+            counts: vec![0; dial.size() as usize],
+        }
+    }
+
+    // Every rotation scores a fixed amount plus one for a range of
+    // positions it starts from (e.g. R7 on a 100 dial with target 0 clicks
+    // on it from 93..=99), so the table is filled with a difference array
+    // instead of simulating every operation from every position.
+    pub fn of(dial: &Dial, operations: &[Operation], counting: Counting) -> Transform {
+        let size = u64::from(dial.size());
+        let target = u64::from(dial.target());
+        let mut always = 0;
+        // diff[i] - diff[i-1] is how the count changes from position i-1 to i
+        let mut diff = vec![0i64; dial.size() as usize + 1];
+        // add one to the positions from..from+len, wrapping around the dial
+        let mut add = |from: u64, len: u64| {
+            if len == 0 {
+                return;
+            }
+            let to = from + len;
+            if to <= size {
+                diff[from as usize] += 1;
+                diff[to as usize] -= 1;
+            } else {
+                diff[from as usize] += 1;
+                diff[size as usize] -= 1;
+                diff[0] += 1;
+                diff[(to - size) as usize] -= 1;
+            }
+        };
+
+        // how far the dial has turned, as an entry position p is now at p + offset
+        let mut offset = 0;
+        for operation in operations {
+            let (forward, dist) = match *operation {
+                Operation::R(dist) => (true, u64::from(dist)),
+                Operation::L(dist) => (false, u64::from(dist)),
+            };
+            // positions are first worked out as where this rotation starts
+            // (q), then moved back by `offset` to entry positions
+            let entry = |q: u64| (q + size - offset) % size;
+            match counting {
+                Counting::EveryClick => {
+                    always += dist / size;
+                    let rest = dist % size;
+                    if forward {
+                        // q + t == target for some t in 1..=rest
+                        add(entry((target + size - rest) % size), rest);
+                    } else {
+                        // q - t == target for some t in 1..=rest
+                        add(entry((target + 1) % size), rest);
+                    }
+                }
+                Counting::EndOfRotation => {
+                    let rest = dist % size;
+                    let q = if forward {
+                        (target + size - rest) % size
+                    } else {
+                        (target + rest) % size
+                    };
+                    add(entry(q), 1);
+                }
+            }
+            offset = if forward {
+                (offset + dist) % size
+            } else {
+                (offset + size - dist % size) % size
+            };
+        }
+
+        let mut counts = Vec::with_capacity(size as usize);
+        let mut running = 0;
+        for change in &diff[..size as usize] {
+            running += change;
+            counts.push(always + running as u64);
+        }
+        Transform {
+            offset: offset as u32,
+            counts,
+        }
+    }
+
+    // `self` followed by `next`
+    pub fn then(&self, next: &Transform) -> Transform {
+        let size = self.counts.len();
+        let counts = (0..size)
+            .map(|entry| {
+                let middle = (entry + self.offset as usize) % size;
+                self.counts[entry] + next.counts[middle]
+            })
+            .collect();
+        Transform {
+            offset: ((self.offset as usize + next.offset as usize) % size) as u32,
+            counts,
+        }
+    }
+
+    // like `Dial::simulate`, a position past the dial is taken modulo its size
+    pub fn apply(&self, position: u32) -> Execution {
+        let size = self.counts.len() as u64;
+        let entry = u64::from(position) % size;
+        Execution {
+            position: ((entry + u64::from(self.offset)) % size) as u32,
+            password: self.counts[entry as usize],
+        }
+    }
+}
+
+impl Dial {
+    // the same as running the operations one by one, spread over rayon's
+    // thread pool
+    pub fn par_execute(&self, operations: &[Operation], counting: Counting) -> Execution {
+        if self.size() > MAX_PARALLEL_SIZE {
+            let start = Execution {
+                position: self.start(),
+                password: 0,
+            };
+            return operations.iter().fold(start, |execution, operation| {
+                let rotation = self.simulate(execution.position, operation);
+                Execution {
+                    position: rotation.end,
+                    password: execution.password + u64::from(counting.count(&rotation)),
+                }
+            });
+        }
+        // each chunk costs a table of `size` entries, so chunks are kept
+        // well above that
+        let chunk = (self.size() as usize * 4).max(1 << 16);
+        operations
+            .par_chunks(chunk)
+            .map(|chunk| Transform::of(self, chunk, counting))
+            .reduce(|| Transform::identity(self), |a, b| a.then(&b))
+            .apply(self.start())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Gen;
+    use crate::{Day1, oracle};
+    use solution::Solution;
+
+    fn sequential(dial: &Dial, operations: &[Operation], counting: Counting) -> Execution {
+        let mut execution = Execution {
+            position: dial.start(),
+            password: 0,
+        };
+        for step in dial.trace(operations, counting) {
+            execution = Execution {
+                position: step.end,
+                password: u64::from(step.password),
+            };
+        }
+        execution
+    }
+
+    #[test]
+    fn transform_test() {
+        let dial = Dial::default();
+        let transform = Transform::of(&dial, &[Operation::R(7)], Counting::EveryClick);
+        assert_eq!(7, transform.offset);
+        let hits: Vec<usize> = (0..100).filter(|&p| transform.counts[p] == 1).collect();
+        assert_eq!((93..100).collect::<Vec<_>>(), hits);
+
+        let transform = Transform::of(&dial, &[Operation::L(250)], Counting::EveryClick);
+        assert_eq!(
+            Execution {
+                position: 0,
+                password: 3
+            },
+            transform.apply(50)
+        );
+        assert_eq!(
+            Execution {
+                position: 10,
+                password: 2
+            },
+            transform.apply(60)
+        );
+    }
+
+    #[test]
+    fn apply_past_the_dial_test() {
+        let dial = Dial::default();
+        let operations = [Operation::L(250), Operation::R(3)];
+        let transform = Transform::of(&dial, &operations, Counting::EveryClick);
+        for position in [100, 250, u32::MAX] {
+            let mut execution = Execution {
+                position,
+                password: 0,
+            };
+            for operation in &operations {
+                let rotation = dial.simulate(execution.position, operation);
+                execution.password += u64::from(Counting::EveryClick.count(&rotation));
+                execution.position = rotation.end;
+            }
+            assert_eq!(execution, transform.apply(position), "{}", position);
+        }
+    }
+
+    #[test]
+    fn transform_matches_oracle_test() {
+        let mut random = Gen::new(0x5eed_1e55_f00d_d1a1);
+        for case in 0..50 {
+            let dial = random.dial();
+            let operations = random.operations(30);
+            for counting in [Counting::EndOfRotation, Counting::EveryClick] {
+                let transform = Transform::of(&dial, &operations, counting);
+                let entry = random.below(dial.size());
+                let shifted = Dial::new(dial.size(), entry, dial.target()).unwrap();
+                assert_eq!(
+                    u64::from(oracle::password(&shifted, &operations, counting)),
+                    transform.apply(entry).password,
+                    "case {}: {:?} {:?}",
+                    case,
+                    shifted,
+                    counting
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn then_is_associative_test() {
+        let mut random = Gen::new(0xa550_c1a7_1e00_0001);
+        for case in 0..100 {
+            let dial = random.dial();
+            let [a, b, c] =
+                [(); 3].map(|_| Transform::of(&dial, &random.operations(10), Counting::EveryClick));
+            assert_eq!(a.then(&b).then(&c), a.then(&b.then(&c)), "case {}", case);
+            assert_eq!(a, Transform::identity(&dial).then(&a));
+            assert_eq!(a, a.then(&Transform::identity(&dial)));
+        }
+    }
+
+    #[test]
+    fn par_execute_test() {
+        let input = file_read::read_to_string(Day1::ROOT, Day1::INPUT).unwrap();
+        let operations = Day1::parse(&input).unwrap();
+        // enough copies to be split into several chunks
+        let operations = operations.repeat(100);
+        let mut random = Gen::new(0x0123_4567_89ab_cdef);
+        for dial in [Dial::default(), random.dial(), random.dial()] {
+            for counting in [Counting::EndOfRotation, Counting::EveryClick] {
+                assert_eq!(
+                    sequential(&dial, &operations, counting),
+                    dial.par_execute(&operations, counting),
+                    "{:?} {:?}",
+                    dial,
+                    counting
+                );
+            }
+        }
+        // too big for tables, run one by one
+        let dial = Dial::new(MAX_PARALLEL_SIZE + 1, 7, 65000).unwrap();
+        let operations = &operations[..5000];
+        for counting in [Counting::EndOfRotation, Counting::EveryClick] {
+            assert_eq!(
+                sequential(&dial, operations, counting),
+                dial.par_execute(operations, counting)
+            );
+        }
+    }
+}
@@ -0,0 +1,321 @@
+// Compact scenario language for the dial, a superset of the puzzle input:
+//
+//   # comments run to the end of the line, blank lines are ignored
+//   R10 L5        several statements may share a line
+//   =37           jump straight to position 37
+//   3x(R10 L5)    repeat a block; blocks nest and may span lines
+//
+// A jump makes no clicks, so it never adds to the password under any
+// counting policy; it only changes where the next rotation starts. Scripts
+// that would run more than `Dial::MAX_SCRIPT_STEPS` steps are refused.
+
+use crate::{Counting, Dial, DialError, Operation};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    Rotate(Operation),
+    Set(u32),
+    Repeat(u32, Vec<Statement>),
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Statement::Rotate(operation) => write!(f, "{}", operation),
+            Statement::Set(position) => write!(f, "={}", position),
+            Statement::Repeat(times, body) => {
+                write!(f, "{}x(", times)?;
+                for (index, statement) in body.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", statement)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+struct Token<'a> {
+    line: usize,
+    text: &'a str,
+}
+
+fn tokenize(input: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    for (index, line) in input.lines().enumerate() {
+        let code = line.split_once('#').map_or(line, |(code, _)| code);
+        for word in code.split_whitespace() {
+            // parentheses are tokens of their own, glued to words or not
+            let mut rest = word;
+            while !rest.is_empty() {
+                let end = match rest.find(['(', ')']) {
+                    Some(0) => 1,
+                    Some(end) => end,
+                    None => rest.len(),
+                };
+                tokens.push(Token {
+                    line: index + 1,
+                    text: &rest[..end],
+                });
+                rest = &rest[end..];
+            }
+        }
+    }
+    tokens
+}
+
+pub fn parse_script(input: &str) -> file_read::Result<Vec<Statement>> {
+    let tokens = tokenize(input);
+    let mut position = 0;
+    let statements = parse_block(&tokens, &mut position)?;
+    match tokens.get(position) {
+        Some(token) => Err(file_read::Error::parse(
+            token.line,
+            token.text,
+            "`)` without a matching `(`",
+        )),
+        None => Ok(statements),
+    }
+}
+
+// statements up to the end of input or a `)`, which is left for the caller
+fn parse_block(tokens: &[Token], position: &mut usize) -> file_read::Result<Vec<Statement>> {
+    let mut statements = vec![];
+    while let Some(token) = tokens.get(*position) {
+        if token.text == ")" {
+            break;
+        }
+        *position += 1;
+        let error = |message: &str| file_read::Error::parse(token.line, token.text, message);
+
+        let statement = if let Some(value) = token.text.strip_prefix('=') {
+            let value = value.parse().map_err(|_| error("`=` needs a position"))?;
+            Statement::Set(value)
+        } else if let Some(times) = token.text.strip_suffix('x') {
+            let times = times
+                .parse()
+                .map_err(|_| error("a repeat is written like `3x(R10 L5)`"))?;
+            match tokens.get(*position) {
+                Some(open) if open.text == "(" => *position += 1,
+                _ => return Err(error("repeat count must be followed by `(`")),
+            }
+            let body = parse_block(tokens, position)?;
+            match tokens.get(*position) {
+                Some(close) if close.text == ")" => *position += 1,
+                _ => return Err(error("repeat block is never closed")),
+            }
+            Statement::Repeat(times, body)
+        } else {
+            let operation = token
+                .text
+                .parse()
+                .map_err(|err| file_read::Error::parse(token.line, token.text, err))?;
+            Statement::Rotate(operation)
+        };
+        statements.push(statement);
+    }
+    Ok(statements)
+}
+
+// How many steps running `statements` takes: one per rotation or jump and
+// one per pass of a repeat, so that empty repeats cost something too. None
+// past u64, which is far past any limit.
+fn steps(statements: &[Statement]) -> Option<u64> {
+    statements.iter().try_fold(0u64, |total, statement| {
+        let steps = match statement {
+            Statement::Rotate(_) | Statement::Set(_) => 1,
+            Statement::Repeat(times, body) => u64::from(*times).checked_mul(steps(body)? + 1)?,
+        };
+        total.checked_add(steps)
+    })
+}
+
+// Where a script leaves the dial and what it scored on the way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Execution {
+    pub position: u32,
+    // u64 because streamed inputs can be far larger than the puzzle's
+    pub password: u64,
+}
+
+impl Dial {
+    // `3x(...)` nests, so a few lines can ask for more work than would ever
+    // finish; a billion steps take seconds
+    pub const MAX_SCRIPT_STEPS: u64 = 1 << 30;
+
+    pub fn execute(
+        &self,
+        statements: &[Statement],
+        counting: Counting,
+    ) -> Result<Execution, DialError> {
+        if steps(statements).is_none_or(|steps| steps > Dial::MAX_SCRIPT_STEPS) {
+            return Err(DialError::ScriptTooLong);
+        }
+        let mut execution = Execution {
+            position: self.start(),
+            password: 0,
+        };
+        self.execute_block(statements, counting, &mut execution)?;
+        Ok(execution)
+    }
+
+    fn execute_block(
+        &self,
+        statements: &[Statement],
+        counting: Counting,
+        execution: &mut Execution,
+    ) -> Result<(), DialError> {
+        for statement in statements {
+            match statement {
+                Statement::Rotate(operation) => {
+                    let rotation = self.simulate(execution.position, operation);
+                    execution.password += u64::from(counting.count(&rotation));
+                    execution.position = rotation.end;
+                }
+                Statement::Set(position) if *position < self.size() => {
+                    execution.position = *position;
+                }
+                Statement::Set(position) => return Err(DialError::PositionOutOfRange(*position)),
+                Statement::Repeat(times, body) => {
+                    for _ in 0..*times {
+                        self.execute_block(body, counting, execution)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_script_test() {
+        let script = "# warm up\n\nR10 =37\n2x(L5 3x(R1))  # nested\n";
+        assert_eq!(
+            vec![
+                Statement::Rotate(Operation::R(10)),
+                Statement::Set(37),
+                Statement::Repeat(
+                    2,
+                    vec![
+                        Statement::Rotate(Operation::L(5)),
+                        Statement::Repeat(3, vec![Statement::Rotate(Operation::R(1))]),
+                    ]
+                ),
+            ],
+            parse_script(script).unwrap()
+        );
+    }
+
+    #[test]
+    fn block_spans_lines_test() {
+        let statements = parse_script("2x(\n  R10\n  L5\n)").unwrap();
+        assert_eq!("2x(R10 L5)", statements[0].to_string());
+    }
+
+    #[test]
+    fn parse_script_errors_test() {
+        let cases = [
+            (
+                "R1\nF27",
+                "2: unknown direction `F`, expected L or R in `F27`",
+            ),
+            ("=x", "1: `=` needs a position in `=x`"),
+            ("3x R1", "1: repeat count must be followed by `(` in `3x`"),
+            ("R1\n2x(R1", "2: repeat block is never closed in `2x`"),
+            ("R1 )", "1: `)` without a matching `(` in `)`"),
+            ("ax(R1)", "1: a repeat is written like `3x(R10 L5)` in `ax`"),
+        ];
+        for (script, message) in cases {
+            assert_eq!(message, parse_script(script).unwrap_err().to_string());
+        }
+    }
+
+    #[test]
+    fn execute_test() {
+        let dial = Dial::default();
+        // the puzzle example, written compactly
+        let script = parse_script(
+            "L68 L30 R48 L5 R60\n\
+             L55 L1 L99 R14 L82",
+        )
+        .unwrap();
+        let execution = dial.execute(&script, Counting::EveryClick).unwrap();
+        assert_eq!(
+            Execution {
+                position: 32,
+                password: 6
+            },
+            execution
+        );
+
+        // each R100 from 0 clicks on 0 once, the jump clicks on nothing
+        let script = parse_script("=0 3x(R100) =0").unwrap();
+        let execution = dial.execute(&script, Counting::EveryClick).unwrap();
+        assert_eq!(
+            Execution {
+                position: 0,
+                password: 3
+            },
+            execution
+        );
+        let execution = dial.execute(&script, Counting::EndOfRotation).unwrap();
+        assert_eq!(3, execution.password);
+
+        let script = parse_script("=100").unwrap();
+        assert_eq!(
+            Err(DialError::PositionOutOfRange(100)),
+            dial.execute(&script, Counting::EveryClick)
+        );
+    }
+
+    #[test]
+    fn too_long_test() {
+        assert_eq!(
+            Some(2 * (1 + 3 * (1 + 1) + 1) + 1),
+            steps(&parse_script("2x(L5 3x(R1)) =3").unwrap())
+        );
+        // empty repeats still take their passes
+        assert_eq!(Some(7), steps(&parse_script("7x()").unwrap()));
+        // four lines, but more steps than a u64 counts
+        let script = parse_script("4294967295x(\n4294967295x(\nR1\n))").unwrap();
+        assert_eq!(None, steps(&script));
+        let dial = Dial::default();
+        assert_eq!(
+            Err(DialError::ScriptTooLong),
+            dial.execute(&script, Counting::EveryClick)
+        );
+        assert_eq!(
+            Err(DialError::ScriptTooLong),
+            dial.execute(
+                &parse_script("2000000000x()").unwrap(),
+                Counting::EveryClick
+            )
+        );
+    }
+
+    #[test]
+    fn scenario_file_test() {
+        let input = file_read::read_to_string(env!("CARGO_MANIFEST_DIR"), "scenario_example");
+        let script = parse_script(&input.unwrap()).unwrap();
+        let dial = Dial::default();
+        assert_eq!(
+            3,
+            dial.execute(&script, Counting::EndOfRotation)
+                .unwrap()
+                .password
+        );
+        assert_eq!(
+            6,
+            dial.execute(&script, Counting::EveryClick)
+                .unwrap()
+                .password
+        );
+    }
+}
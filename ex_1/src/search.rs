@@ -0,0 +1,265 @@
+// The dial run backwards: instead of counting what a list of operations
+// scores, find the fewest operations that score a given password and/or leave
+// the dial at a given position. Handy for making inputs with known answers.
+//
+// Breadth-first search over (position, password so far). The password never
+// goes down, so states past the wanted password are dropped, and every state
+// is expanded with each distance up to `max_distance` in both directions.
+// The states are kept in flat tables of size * (password + 1) entries, so a
+// search that would need more than `max_states` of them isn't started, and
+// neither is one that could simulate more than `max_steps` rotations, each
+// state times 2 * `max_distance`: an unreachable goal expands all of them.
+
+use crate::{Counting, Dial, Operation};
+use std::collections::VecDeque;
+
+// What the operations have to achieve; `None` means "don't care".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Goal {
+    pub password: Option<u32>,
+    pub end: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Search {
+    dial: Dial,
+    counting: Counting,
+    max_distance: u16,
+    max_states: usize,
+    max_steps: u64,
+}
+
+impl Search {
+    // rotations of up to one full turn; longer ones only matter when a
+    // single rotation should score several clicks
+    pub fn new(dial: Dial, counting: Counting) -> Search {
+        Search {
+            dial,
+            counting,
+            max_distance: dial.size().min(u32::from(u16::MAX)) as u16,
+            max_states: Search::MAX_STATES,
+            max_steps: Search::MAX_STEPS,
+        }
+    }
+
+    // about 16 million states, a few hundred MB of tables
+    pub const MAX_STATES: usize = 1 << 24;
+
+    // about a billion rotations, some seconds
+    pub const MAX_STEPS: u64 = 1 << 30;
+
+    pub fn max_distance(self, max_distance: u16) -> Search {
+        Search {
+            max_distance,
+            ..self
+        }
+    }
+
+    pub fn max_states(self, max_states: usize) -> Search {
+        Search { max_states, ..self }
+    }
+
+    pub fn max_steps(self, max_steps: u64) -> Search {
+        Search { max_steps, ..self }
+    }
+
+    // a shortest sequence from the dial's start, or None if the goal cannot
+    // be reached with rotations of at most `max_distance`, or not within
+    // `max_states` and `max_steps`
+    pub fn shortest(&self, goal: Goal) -> Option<Vec<Operation>> {
+        if goal.end.is_some_and(|end| end >= self.dial.size()) {
+            return None;
+        }
+        let size = self.dial.size() as usize;
+        // without a password goal all passwords look the same
+        let passwords = goal.password.map_or(1, |password| password as usize + 1);
+        let states = size
+            .checked_mul(passwords)
+            .filter(|&states| states <= self.max_states)?;
+        (states as u64)
+            .checked_mul(2 * u64::from(self.max_distance))
+            .filter(|&steps| steps <= self.max_steps)?;
+        let state = |position: u32, password: u32| {
+            let password = if goal.password.is_some() { password } else { 0 };
+            password as usize * size + position as usize
+        };
+        let reached = |position: u32, password: u32| {
+            goal.password.is_none_or(|wanted| wanted == password)
+                && goal.end.is_none_or(|wanted| wanted == position)
+        };
+
+        // how each state was first reached, to walk the answer back
+        let mut parent: Vec<Option<(usize, Operation)>> = vec![None; states];
+        let mut seen = vec![false; states];
+        let mut queue = VecDeque::new();
+        let start = self.dial.start();
+        seen[state(start, 0)] = true;
+        queue.push_back((start, 0));
+
+        while let Some((position, password)) = queue.pop_front() {
+            if reached(position, password) {
+                let mut operations = vec![];
+                let mut current = state(position, password);
+                while let Some((previous, operation)) = parent[current] {
+                    operations.push(operation);
+                    current = previous;
+                }
+                operations.reverse();
+                return Some(operations);
+            }
+            let current = state(position, password);
+            for distance in 1..=self.max_distance {
+                for operation in [Operation::R(distance), Operation::L(distance)] {
+                    let rotation = self.dial.simulate(position, &operation);
+                    let scored = password + self.counting.count(&rotation);
+                    if goal.password.is_some_and(|wanted| scored > wanted) {
+                        continue;
+                    }
+                    let next = state(rotation.end, scored);
+                    if !seen[next] {
+                        seen[next] = true;
+                        parent[next] = Some((current, operation));
+                        queue.push_back((rotation.end, scored));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::Gen;
+    use crate::oracle;
+
+    fn end(dial: &Dial, operations: &[Operation]) -> u32 {
+        operations.iter().fold(dial.start(), |position, operation| {
+            dial.rotate(position, operation)
+        })
+    }
+
+    #[test]
+    fn shortest_test() {
+        let dial = Dial::default();
+        let search = Search::new(dial, Counting::EveryClick);
+        assert_eq!(Some(vec![]), search.shortest(Goal::default()));
+        let goal = Goal {
+            password: Some(1),
+            end: None,
+        };
+        assert_eq!(Some(vec![Operation::R(50)]), search.shortest(goal));
+        let goal = Goal {
+            password: None,
+            end: Some(37),
+        };
+        assert_eq!(Some(vec![Operation::L(13)]), search.shortest(goal));
+
+        // a turn scores at most one click, unless longer rotations are allowed
+        let goal = Goal {
+            password: Some(3),
+            end: Some(50),
+        };
+        assert_eq!(3, search.shortest(goal).unwrap().len());
+        let search = search.max_distance(300);
+        assert_eq!(Some(vec![Operation::R(300)]), search.shortest(goal));
+    }
+
+    #[test]
+    fn unreachable_test() {
+        let dial = Dial::default();
+        let search = Search::new(dial, Counting::EveryClick).max_distance(0);
+        let goal = Goal {
+            password: None,
+            end: Some(0),
+        };
+        assert_eq!(None, search.shortest(goal));
+        // landing on 0 without ever clicking on it
+        let goal = Goal {
+            password: Some(0),
+            end: Some(0),
+        };
+        assert_eq!(None, Search::new(dial, Counting::EveryClick).shortest(goal));
+    }
+
+    #[test]
+    fn too_many_states_test() {
+        let search = Search::new(Dial::default(), Counting::EveryClick);
+        let goal = Goal {
+            password: Some(u32::MAX),
+            end: None,
+        };
+        assert_eq!(None, search.shortest(goal));
+        let goal = Goal {
+            password: Some(3),
+            end: None,
+        };
+        // 100 positions times 4 passwords
+        assert_eq!(None, search.max_states(399).shortest(goal));
+        assert!(search.max_states(400).shortest(goal).is_some());
+        // and 400 states times 100 distances both ways
+        assert_eq!(None, search.max_steps(79_999).shortest(goal));
+        assert!(search.max_steps(80_000).shortest(goal).is_some());
+        // the default distance on a big dial is too much work to even try
+        let dial = Dial::new(1_000_000, 0, 0).unwrap();
+        let goal = Goal {
+            password: None,
+            end: Some(1),
+        };
+        assert_eq!(None, Search::new(dial, Counting::EveryClick).shortest(goal));
+        assert!(
+            Search::new(dial, Counting::EveryClick)
+                .max_distance(10)
+                .shortest(goal)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn end_out_of_range_test() {
+        let search = Search::new(Dial::default(), Counting::EveryClick);
+        for end in [100, u32::MAX] {
+            let goal = Goal {
+                password: None,
+                end: Some(end),
+            };
+            assert_eq!(None, search.shortest(goal));
+        }
+    }
+
+    #[test]
+    fn shortest_matches_oracle_test() {
+        let mut random = Gen::new(0x0dd_ba11_cafe_f00d);
+        for case in 0..100 {
+            let dial = random.dial();
+            let counting = if random.below(2) == 0 {
+                Counting::EndOfRotation
+            } else {
+                Counting::EveryClick
+            };
+            // a goal that a short random walk is known to reach
+            let walk: Vec<Operation> = (0..random.below(5))
+                .map(|_| {
+                    let distance = random.below(dial.size()) as u16 + 1;
+                    if random.below(2) == 0 {
+                        Operation::L(distance)
+                    } else {
+                        Operation::R(distance)
+                    }
+                })
+                .collect();
+            let goal = Goal {
+                password: Some(oracle::password(&dial, &walk, counting)),
+                end: Some(end(&dial, &walk)),
+            };
+            let found = Search::new(dial, counting).shortest(goal).unwrap();
+            assert!(found.len() <= walk.len(), "case {}: {:?}", case, dial);
+            assert_eq!(
+                goal.password,
+                Some(oracle::password(&dial, &found, counting))
+            );
+            assert_eq!(goal.end, Some(end(&dial, &found)));
+        }
+    }
+}
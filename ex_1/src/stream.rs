@@ -0,0 +1,287 @@
+// The dial over inputs too big to read into a String, e.g. generated stress
+// files of several GB. Bytes go through one reusable buffer and each line is
+// parsed in place, so memory stays flat and nothing is allocated per line.
+//
+// Lines that are not a plain `L`/`R` and a number fall back to
+// `Operation::from_str`, so a bad line fails with the same message as in
+// `Day1::parse`, and the line numbers match too. A line can be at most
+// `MAX_LINE` bytes; a longer one is skipped up to its newline without being
+// kept and reported as bad, as no operation is that long.
+
+use crate::{Counting, Dial, Execution, Operation};
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+const BUFFER: usize = 64 * 1024;
+
+// a line has to fit in the buffer, newline included
+const MAX_LINE: usize = BUFFER - 1;
+
+// where read errors say they came from; parse errors have no path, use
+// `with_path` like for the other parsers
+const STREAM_PATH: &str = "<stream>";
+
+impl Dial {
+    pub fn stream(
+        &self,
+        mut reader: impl Read,
+        counting: Counting,
+    ) -> file_read::Result<Execution> {
+        let mut execution = Execution {
+            position: self.start(),
+            password: 0,
+        };
+        let mut buffer = vec![0; BUFFER];
+        // bytes at the front of `buffer` that are not a whole line yet
+        let mut kept = 0;
+        let mut line = 0;
+        loop {
+            if kept == buffer.len() {
+                // it should fail with its line number, not by running out
+                return Err(skip_long_line(&mut reader, &buffer, line + 1));
+            }
+            let read = match reader.read(&mut buffer[kept..]) {
+                Ok(read) => read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(read_error(err)),
+            };
+            let filled = kept + read;
+            let mut from = 0;
+            while let Some(newline) = buffer[from..filled].iter().position(|&b| b == b'\n') {
+                line += 1;
+                self.step(
+                    &buffer[from..from + newline],
+                    line,
+                    counting,
+                    &mut execution,
+                )?;
+                from += newline + 1;
+            }
+            if read == 0 {
+                // like str::lines, a last line needs no newline
+                if from < filled {
+                    self.step(&buffer[from..filled], line + 1, counting, &mut execution)?;
+                }
+                return Ok(execution);
+            }
+            buffer.copy_within(from..filled, 0);
+            kept = filled - from;
+        }
+    }
+
+    fn step(
+        &self,
+        bytes: &[u8],
+        line: usize,
+        counting: Counting,
+        execution: &mut Execution,
+    ) -> file_read::Result<()> {
+        let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+        let operation = match parse_operation(bytes) {
+            Some(operation) => operation,
+            None => slow_parse(bytes, line)?,
+        };
+        let rotation = self.simulate(execution.position, &operation);
+        execution.password += u64::from(counting.count(&rotation));
+        execution.position = rotation.end;
+        Ok(())
+    }
+}
+
+// the common case: a direction and at most five digits that fit in u16
+fn parse_operation(bytes: &[u8]) -> Option<Operation> {
+    let (&direction, digits) = bytes.split_first()?;
+    if digits.is_empty() || digits.len() > 5 {
+        return None;
+    }
+    let mut dist: u32 = 0;
+    for &digit in digits {
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+        dist = dist * 10 + u32::from(digit - b'0');
+    }
+    let dist = u16::try_from(dist).ok()?;
+    match direction {
+        b'L' => Some(Operation::L(dist)),
+        b'R' => Some(Operation::R(dist)),
+        _ => None,
+    }
+}
+
+// reads past the rest of a line that didn't fit in `buffer`
+fn skip_long_line(reader: &mut impl Read, buffer: &[u8], line: usize) -> file_read::Error {
+    let mut rest = [0; 4096];
+    loop {
+        match reader.read(&mut rest) {
+            Ok(0) => break,
+            Ok(read) if rest[..read].contains(&b'\n') => break,
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return read_error(err),
+        }
+    }
+    let start = String::from_utf8_lossy(&buffer[..buffer.len().min(16)]);
+    file_read::Error::parse(
+        line,
+        &format!("{}...", start),
+        format!("line is longer than {} bytes", MAX_LINE),
+    )
+}
+
+fn slow_parse(bytes: &[u8], line: usize) -> file_read::Result<Operation> {
+    let text = std::str::from_utf8(bytes).map_err(|_| file_read::Error::Decode {
+        path: PathBuf::from(STREAM_PATH),
+        line,
+    })?;
+    text.parse()
+        .map_err(|err| file_read::Error::parse(line, text, err))
+}
+
+fn read_error(err: io::Error) -> file_read::Error {
+    file_read::Error::Io {
+        path: PathBuf::from(STREAM_PATH),
+        source: err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day1;
+    use solution::Solution;
+
+    // hands out at most `chunk` bytes per read, to split lines anywhere
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        chunk: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = self.chunk.min(buf.len()).min(self.bytes.len());
+            buf[..len].copy_from_slice(&self.bytes[..len]);
+            self.bytes = &self.bytes[len..];
+            Ok(len)
+        }
+    }
+
+    fn stream(input: &str, counting: Counting) -> file_read::Result<Execution> {
+        Dial::default().stream(input.as_bytes(), counting)
+    }
+
+    #[test]
+    fn stream_matches_parse_test() {
+        for name in [Day1::EXAMPLE, Day1::INPUT] {
+            let input = file_read::read_to_string(Day1::ROOT, name).unwrap();
+            let operations = Day1::parse(&input).unwrap();
+            let last = Dial::default()
+                .trace(&operations, Counting::EveryClick)
+                .last();
+            let expected = last.map(|step| Execution {
+                position: step.end,
+                password: u64::from(step.password),
+            });
+            for chunk in [1, 3, 7, BUFFER] {
+                let reader = Trickle {
+                    bytes: input.as_bytes(),
+                    chunk,
+                };
+                let execution = Dial::default()
+                    .stream(reader, Counting::EveryClick)
+                    .unwrap();
+                assert_eq!(expected, Some(execution), "{} in chunks of {}", name, chunk);
+            }
+        }
+        let input = file_read::read_to_string(Day1::ROOT, Day1::INPUT).unwrap();
+        assert_eq!(
+            1154,
+            stream(&input, Counting::EndOfRotation).unwrap().password
+        );
+    }
+
+    #[test]
+    fn line_endings_test() {
+        let expected = stream("L68\nL30\nR48", Counting::EveryClick).unwrap();
+        assert_eq!(
+            Execution {
+                position: 0,
+                password: 2
+            },
+            expected
+        );
+        assert_eq!(
+            expected,
+            stream("L68\r\nL30\r\nR48\r\n", Counting::EveryClick).unwrap()
+        );
+        assert_eq!(
+            expected,
+            stream("L68\nL30\nR48\n", Counting::EveryClick).unwrap()
+        );
+        assert_eq!(
+            Execution {
+                position: 50,
+                password: 0
+            },
+            stream("", Counting::EveryClick).unwrap()
+        );
+    }
+
+    #[test]
+    fn stream_errors_test() {
+        for input in [
+            "R1\nF27\n",
+            "R1\n\nL2",
+            "L65536",
+            "R1\nR-5",
+            "L1 ",
+            "L00000001\nx",
+        ] {
+            let expected = Day1::parse(input).unwrap_err().to_string();
+            let found = stream(input, Counting::EveryClick).unwrap_err().to_string();
+            assert_eq!(expected, found, "{:?}", input);
+        }
+        let err = Dial::default()
+            .stream(&b"R1\nL\xff2\n"[..], Counting::EveryClick)
+            .unwrap_err();
+        assert_eq!("<stream>:2: not valid UTF-8", err.to_string());
+
+        // far longer than the buffer, still reported as a bad line
+        let long = format!("R1\nR{}\n", "9".repeat(3 * BUFFER));
+        let err = stream(&long, Counting::EveryClick).unwrap_err();
+        assert!(matches!(err, file_read::Error::Parse { line: 2, .. }));
+    }
+
+    #[test]
+    fn long_line_test() {
+        // just fits, and as u16 doesn't mind leading zeros it's an operation
+        let fits = format!("R{:0>1$}\nL2", 1, MAX_LINE - 1);
+        assert_eq!(
+            Execution {
+                position: 49,
+                password: 0
+            },
+            stream(&fits, Counting::EveryClick).unwrap()
+        );
+        // one byte more, in whatever chunks it comes, without a newline too
+        for input in [
+            format!("R1\nR{:0>1$}\nL2", 1, MAX_LINE),
+            format!("R1\n{}", "\u{0}".repeat(10 * BUFFER)),
+        ] {
+            for chunk in [1000, BUFFER] {
+                let reader = Trickle {
+                    bytes: input.as_bytes(),
+                    chunk,
+                };
+                let err = Dial::default()
+                    .stream(reader, Counting::EveryClick)
+                    .unwrap_err();
+                assert!(matches!(err, file_read::Error::Parse { line: 2, .. }));
+                assert!(
+                    err.to_string()
+                        .starts_with("2: line is longer than 65535 bytes")
+                );
+            }
+        }
+    }
+}
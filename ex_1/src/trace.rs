@@ -0,0 +1,133 @@
+use crate::{Counting, Dial, Operation};
+
+// One rotation as it happened, for diffing runs or feeding plotting tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    // 0-based position of the operation in the input
+    pub index: usize,
+    pub start: u32,
+    pub operation: Operation,
+    pub end: u32,
+    // what the counting policy scored for this rotation
+    pub passes: u32,
+    // passes so far, this step included
+    pub password: u32,
+}
+
+impl Step {
+    pub const CSV_HEADER: &str = "index,start,operation,end,passes,password";
+
+    pub fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.index, self.start, self.operation, self.end, self.passes, self.password
+        )
+    }
+
+    // one JSON object, meant to be written one per line
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"index\":{},\"start\":{},\"operation\":\"{}\",\"end\":{},\"passes\":{},\"password\":{}}}",
+            self.index, self.start, self.operation, self.end, self.passes, self.password
+        )
+    }
+}
+
+pub struct Trace<'a> {
+    dial: Dial,
+    counting: Counting,
+    operations: std::slice::Iter<'a, Operation>,
+    index: usize,
+    position: u32,
+    password: u32,
+}
+
+impl Iterator for Trace<'_> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        let operation = *self.operations.next()?;
+        let start = self.position;
+        let rotation = self.dial.simulate(start, &operation);
+        let passes = self.counting.count(&rotation);
+        self.password += passes;
+        self.position = rotation.end;
+        let step = Step {
+            index: self.index,
+            start,
+            operation,
+            end: rotation.end,
+            passes,
+            password: self.password,
+        };
+        self.index += 1;
+        Some(step)
+    }
+}
+
+impl Dial {
+    // every step of `password`; the last step's password is its result
+    pub fn trace<'a>(&self, operations: &'a [Operation], counting: Counting) -> Trace<'a> {
+        Trace {
+            dial: *self,
+            counting,
+            operations: operations.iter(),
+            index: 0,
+            position: self.start(),
+            password: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_test() {
+        let operations = [Operation::L(68), Operation::L(30), Operation::R(48)];
+        let steps: Vec<Step> = Dial::default()
+            .trace(&operations, Counting::EveryClick)
+            .collect();
+        assert_eq!(3, steps.len());
+        assert_eq!(
+            Step {
+                index: 0,
+                start: 50,
+                operation: Operation::L(68),
+                end: 82,
+                passes: 1,
+                password: 1,
+            },
+            steps[0]
+        );
+        let summary: Vec<(u32, u32, u32, u32)> = steps
+            .iter()
+            .map(|step| (step.start, step.end, step.passes, step.password))
+            .collect();
+        assert_eq!(vec![(50, 82, 1, 1), (82, 52, 0, 1), (52, 0, 1, 2)], summary);
+        assert_eq!(
+            Dial::default().count_passes(&operations),
+            steps.last().unwrap().password
+        );
+
+        let landings: Vec<u32> = Dial::default()
+            .trace(&operations, Counting::EndOfRotation)
+            .map(|step| step.passes)
+            .collect();
+        assert_eq!(vec![0, 0, 1], landings);
+    }
+
+    #[test]
+    fn render_test() {
+        let step = Dial::default()
+            .trace(&[Operation::L(68)], Counting::EveryClick)
+            .next()
+            .unwrap();
+        assert_eq!("0,50,L68,82,1,1", step.to_csv());
+        assert_eq!(
+            r#"{"index":0,"start":50,"operation":"L68","end":82,"passes":1,"password":1}"#,
+            step.to_json()
+        );
+    }
+}
@@ -0,0 +1,176 @@
+// The digit math of parts 1 and 2 on the real input, once on the numbers
+// themselves (`ex_2::digits`) and once the way it used to be done, through
+// `to_string()` and `parse()`. Both have to find the same IDs.
+//
+//   cargo bench -p ex_2 --bench digits
+
+use ex_2::{IdRange, part1, part2};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+fn ranges() -> Vec<IdRange> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/input/ranges_final");
+    fs::read_to_string(path)
+        .expect("input is there")
+        .trim()
+        .split(',')
+        .map(|range| range.parse().expect("input is valid"))
+        .collect()
+}
+
+// part 1 and 2 as they were before the digits module, copied as they were,
+// print in part 2 and all
+mod text {
+    pub mod part1 {
+        use ex_2::Id;
+
+        pub fn collect_invalid_ids_for_range(start: u64, end: u64) -> Vec<u64> {
+            let mut invalid_ids: Vec<u64> = vec![];
+            let mut current_number = start;
+            if check_range(Id(start), Id(end)) {
+                while current_number <= end {
+                    current_number = bump_to_even_digits(Id(current_number)).0;
+                    if has_a_pattern(current_number) {
+                        invalid_ids.push(current_number)
+                    } else {
+                        current_number += 1;
+                        continue;
+                    }
+                    if let Some(val) = next_posible_patterned_number(current_number) {
+                        current_number = val
+                    }
+                    if current_number > end {
+                        return invalid_ids;
+                    }
+                }
+            }
+            invalid_ids
+        }
+
+        fn has_a_pattern(number: u64) -> bool {
+            let num_as_str = number.to_string();
+            let (left, right) = num_as_str.split_at(num_as_str.len() / 2);
+            left == right
+        }
+
+        fn next_posible_patterned_number(current_number: u64) -> Option<u64> {
+            let current_number = current_number.to_string();
+            if current_number.len() % 2 == 1 {
+                return None;
+            };
+
+            let (current_pattern_part, _) = current_number.split_at(current_number.len() / 2);
+            let next_pattern_part: u64 =
+                current_pattern_part.parse::<u64>().expect("not integer") + 1;
+            let new_number: u64 = next_pattern_part
+                .to_string()
+                .repeat(2)
+                .parse()
+                .expect("not integer");
+            Some(new_number)
+        }
+
+        // digit counts come from the numbers, never from how they were written
+        fn bump_to_even_digits(n: Id) -> Id {
+            let digits = n.digits();
+            if digits.is_multiple_of(2) {
+                n
+            } else {
+                Id(10u64.pow(digits))
+            }
+        }
+
+        fn check_range(start: Id, end: Id) -> bool {
+            let (start, end) = (start.digits(), end.digits());
+            start.is_multiple_of(2) || end.is_multiple_of(2) || start != end
+        }
+    }
+
+    pub mod part2 {
+        pub fn collect_invalid_ids_for_range(start: u64, end: u64) -> Vec<u64> {
+            let mut invalid_ids: Vec<u64> = vec![];
+            for current_number in start..=end {
+                if has_a_pattern(current_number) {
+                    invalid_ids.push(current_number)
+                }
+            }
+            invalid_ids
+        }
+
+        fn has_a_pattern(number: u64) -> bool {
+            let num_as_str = number.to_string();
+            let mut chunk = num_as_str.len() / 2;
+            while chunk > 0 {
+                if find_pattern(&num_as_str, chunk) {
+                    println!("{} {}", chunk, number);
+                    return true;
+                } else {
+                    chunk -= 1
+                }
+            }
+            false
+        }
+
+        fn find_pattern(pattern: &str, chunk: usize) -> bool {
+            if chunk == 0 {
+                false
+            } else {
+                let chunks = (0..pattern.len()).step_by(chunk).map(|i| {
+                    let end = (i + chunk).min(pattern.len());
+                    &pattern[i..end]
+                });
+                // dbg!(chunks.clone().collect::<Vec<&str>>());
+                all_equal(chunks)
+            }
+        }
+
+        // lifetime 'a is needed because otherwise 'static will be used
+        fn all_equal<'a>(mut iter: impl Iterator<Item = &'a str>) -> bool {
+            if let Some(first) = iter.next() {
+                // `first` is captured and then passed to `all` method
+                iter.all(|s| s == first)
+            } else {
+                true
+            }
+        }
+    }
+}
+
+fn time(name: &str, ranges: &[IdRange], collect: fn(u64, u64) -> Vec<u64>) -> (Vec<u64>, f64) {
+    let started = Instant::now();
+    let ids: Vec<u64> = ranges
+        .iter()
+        .flat_map(|range| collect(range.start, range.end))
+        .collect();
+    let seconds = started.elapsed().as_secs_f64();
+    println!(
+        "{:>12}: {:8.2} ms, {} invalid IDs summing to {}",
+        name,
+        seconds * 1000.0,
+        ids.len(),
+        ids.iter().map(|&id| u128::from(id)).sum::<u128>()
+    );
+    (ids, seconds)
+}
+
+fn main() {
+    let ranges = ranges();
+    for (part, digits, text) in [
+        (
+            "part1",
+            part1::collect_invalid_ids_for_range as fn(u64, u64) -> Vec<u64>,
+            text::part1::collect_invalid_ids_for_range as fn(u64, u64) -> Vec<u64>,
+        ),
+        (
+            "part2",
+            part2::collect_invalid_ids_for_range,
+            text::part2::collect_invalid_ids_for_range,
+        ),
+    ] {
+        let (by_digits, digits_seconds) = time(&format!("{} digits", part), &ranges, digits);
+        let (by_text, text_seconds) = time(&format!("{} text", part), &ranges, text);
+        assert_eq!(by_text, by_digits);
+        println!("{:>12}: {:.1}x faster", part, text_seconds / digits_seconds);
+    }
+}
@@ -0,0 +1,613 @@
+// IDs with more digits than a u64 (or u128) holds. There is no big integer
+// crate in the workspace, and the closed form only needs +, -, * and halving,
+// so this is a small decimal BigUint: base 10^9 limbs, least significant
+// first, without zero limbs at the top (zero has no limbs at all).
+//
+// The closed form needs the first and last block for each block length; for
+// u64 those come from divisions, here they come from the digits themselves:
+// the first block is the leading digits of the low end, plus one if
+// repeating them falls short of it.
+
+use crate::closed_form::invalid_of_length;
+use crate::id;
+use crate::{IdPolicy, IdRange, ParseRangeError, RangeSet, Tally, tally};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    fn normalized(mut limbs: Vec<u32>) -> BigUint {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        let mut value: u128 = 0;
+        for &limb in self.limbs.iter().rev() {
+            value = value
+                .checked_mul(u128::from(BASE))?
+                .checked_add(u128::from(limb))?;
+        }
+        Some(value)
+    }
+
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0;
+        for (i, &limb) in self.limbs.iter().enumerate() {
+            let mut value = i64::from(limb) - borrow - i64::from(*other.limbs.get(i).unwrap_or(&0));
+            borrow = 0;
+            if value < 0 {
+                value += BASE as i64;
+                borrow = 1;
+            }
+            limbs.push(value as u32);
+        }
+        Some(BigUint::normalized(limbs))
+    }
+
+    // rounding down
+    pub fn half(&self) -> BigUint {
+        let mut limbs = self.limbs.clone();
+        let mut carry = 0;
+        for limb in limbs.iter_mut().rev() {
+            let value = carry * BASE + u64::from(*limb);
+            *limb = (value / 2) as u32;
+            carry = value % 2;
+        }
+        BigUint::normalized(limbs)
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut value: u128) -> BigUint {
+        let mut limbs = vec![];
+        while value > 0 {
+            limbs.push((value % u128::from(BASE)) as u32);
+            value /= u128::from(BASE);
+        }
+        BigUint { limbs }
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+        for i in 0..len {
+            let value = u64::from(*self.limbs.get(i).unwrap_or(&0))
+                + u64::from(*other.limbs.get(i).unwrap_or(&0))
+                + carry;
+            limbs.push((value % BASE) as u32);
+            carry = value / BASE;
+        }
+        limbs.push(carry as u32);
+        BigUint::normalized(limbs)
+    }
+}
+
+// only ever used where the result can't go below zero
+impl Sub for BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: BigUint) -> BigUint {
+        self.checked_sub(&other)
+            .expect("BigUint subtraction below zero")
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: BigUint) -> BigUint {
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len() + 1];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let value = limbs[i + j] + u64::from(a) * u64::from(b) + carry;
+                limbs[i + j] = value % BASE;
+                carry = value / BASE;
+            }
+            limbs[i + other.limbs.len()] += carry;
+        }
+        BigUint::normalized(limbs.into_iter().map(|limb| limb as u32).collect())
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.limbs.split_last() {
+            None => write!(f, "0"),
+            Some((top, rest)) => {
+                write!(f, "{}", top)?;
+                for limb in rest.iter().rev() {
+                    write!(f, "{:09}", limb)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigUintError;
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "must be a non-negative integer")
+    }
+}
+
+impl Error for ParseBigUintError {}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigUintError);
+        }
+        let limbs = s
+            .as_bytes()
+            .rchunks(BASE_DIGITS)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |limb, &digit| limb * 10 + u32::from(digit - b'0'))
+            })
+            .collect();
+        Ok(BigUint::normalized(limbs))
+    }
+}
+
+// A `start-end` range of any size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigRange {
+    pub start: BigUint,
+    pub end: BigUint,
+}
+
+impl fmt::Display for BigRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+// the same rules as IdRange, just without the 64 bit limit
+impl FromStr for BigRange {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').ok_or(ParseRangeError::MissingDash)?;
+        BigRange::from_bounds(start, end)
+    }
+}
+
+impl BigRange {
+    pub(crate) fn from_bounds(start: &str, end: &str) -> Result<BigRange, ParseRangeError> {
+        match (start.is_empty(), end.is_empty()) {
+            (true, false) => return Err(ParseRangeError::MissingStart),
+            (false, true) => return Err(ParseRangeError::MissingEnd),
+            _ => {}
+        }
+        // written like any ID, only the u64 limit doesn't apply
+        let bound = |s: &str| match id::canonical(s).0.first() {
+            Some(&issue) => Err(ParseRangeError::from(issue)),
+            None => Ok(s.parse().expect("only digits")),
+        };
+        let range = BigRange {
+            start: bound(start)?,
+            end: bound(end)?,
+        };
+        if range.end < range.start {
+            return Err(ParseRangeError::BigInverted(range));
+        }
+        Ok(range)
+    }
+}
+
+impl From<IdRange> for BigRange {
+    fn from(range: IdRange) -> BigRange {
+        BigRange {
+            start: BigUint::from(u128::from(range.start)),
+            end: BigUint::from(u128::from(range.end)),
+        }
+    }
+}
+
+// Day 2's input: the ranges that fit in a u64 go through the fast u64 code,
+// the rest (and the part of a range beyond u64::MAX) through BigRange.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ranges {
+    pub ids: RangeSet,
+    // sorted and merged like the RangeSet, all above u64::MAX
+    pub big: Vec<BigRange>,
+}
+
+impl Ranges {
+    pub fn new(input: Vec<BigRange>) -> Ranges {
+        let beyond = BigUint::from(u128::from(u64::MAX) + 1);
+        let mut ids = vec![];
+        let mut big = vec![];
+        for range in input {
+            if range.start < beyond {
+                let start = range.start.to_u128().expect("below 2^64") as u64;
+                let end = range
+                    .end
+                    .to_u128()
+                    .map_or(u64::MAX, |end| end.min(u128::from(u64::MAX)) as u64);
+                ids.push(IdRange { start, end });
+            }
+            if range.end >= beyond {
+                big.push(BigRange {
+                    start: range.start.max(beyond.clone()),
+                    end: range.end,
+                });
+            }
+        }
+
+        big.sort_by(|a, b| a.start.cmp(&b.start));
+        let mut merged: Vec<BigRange> = vec![];
+        for range in big {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end.clone() + BigUint::from(1) => {
+                    if range.end > last.end {
+                        last.end = range.end;
+                    }
+                }
+                _ => merged.push(range),
+            }
+        }
+        Ranges {
+            // all of them go up, so none is inverted
+            ids: RangeSet::new(ids).expect("ranges are not inverted"),
+            big: merged,
+        }
+    }
+
+    // the sum of the invalid IDs, for the repeated block policies
+    pub fn sum_invalid(&self, policy: IdPolicy) -> Result<BigUint, NoClosedForm> {
+        let mut sum = BigUint::default();
+        for &range in self.ids.ranges() {
+            sum = sum + BigUint::from(tally(range, policy).sum);
+        }
+        for range in &self.big {
+            sum = sum + range.tally(policy)?.sum;
+        }
+        Ok(sum)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BigTally {
+    pub count: BigUint,
+    pub sum: BigUint,
+}
+
+impl Add for BigTally {
+    type Output = BigTally;
+
+    fn add(self, other: BigTally) -> BigTally {
+        BigTally {
+            count: self.count + other.count,
+            sum: self.sum + other.sum,
+        }
+    }
+}
+
+impl Sub for BigTally {
+    type Output = BigTally;
+
+    fn sub(self, other: BigTally) -> BigTally {
+        BigTally {
+            count: self.count - other.count,
+            sum: self.sum - other.sum,
+        }
+    }
+}
+
+// a result too big for the fixed size type it was asked for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub value: BigUint,
+    pub limit: &'static str,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} does not fit in {}", self.value, self.limit)
+    }
+}
+
+impl Error for Overflow {}
+
+impl BigTally {
+    // back to fixed size numbers, if they are big enough
+    pub fn to_tally(&self) -> Result<Tally, Overflow> {
+        let count = self
+            .count
+            .to_u128()
+            .and_then(|count| u64::try_from(count).ok())
+            .ok_or_else(|| Overflow {
+                value: self.count.clone(),
+                limit: "u64",
+            })?;
+        let sum = self.sum.to_u128().ok_or_else(|| Overflow {
+            value: self.sum.clone(),
+            limit: "u128",
+        })?;
+        Ok(Tally { count, sum })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoClosedForm(pub IdPolicy);
+
+impl fmt::Display for NoClosedForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "policy {} can only be counted one ID at a time", self.0)
+    }
+}
+
+impl Error for NoClosedForm {}
+
+impl BigRange {
+    // like `tally`, but only for the repeated block policies: checking IDs
+    // one by one is hopeless at these sizes anyway
+    pub fn tally(&self, policy: IdPolicy) -> Result<BigTally, NoClosedForm> {
+        if policy.allows_repeats(2).is_none() {
+            return Err(NoClosedForm(policy));
+        }
+        let mut total = BigTally::default();
+        if self.end < self.start {
+            return Ok(total);
+        }
+        let start = self.start.to_string();
+        let end = self.end.to_string();
+        for digits in start.len().max(1)..=end.len() {
+            // the part of the range with exactly `digits` digits, as text
+            let low = if digits == start.len() && start != "0" {
+                start.clone()
+            } else {
+                format!("1{}", "0".repeat(digits - 1))
+            };
+            let high = if digits == end.len() {
+                end.clone()
+            } else {
+                "9".repeat(digits)
+            };
+            total = total
+                + invalid_of_length(digits as u32, policy, |block| {
+                    repeated(&low, &high, block as usize)
+                });
+        }
+        Ok(total)
+    }
+}
+
+// numbers between `low` and `high`, both `digits` long, made of a `block`
+// digit block repeated
+fn repeated(low: &str, high: &str, block: usize) -> BigTally {
+    let repeats = low.len() / block;
+    let one = BigUint::from(1);
+    let parse = |digits: &str| digits.parse::<BigUint>().expect("only digits");
+
+    let mut first = parse(&low[..block]);
+    if low[..block].repeat(repeats).as_str() < low {
+        first = first + one.clone();
+    }
+    let mut last = parse(&high[..block]);
+    if high[..block].repeat(repeats).as_str() > high {
+        // can't go below zero: the leading digit of `high` is not 0
+        last = last - one.clone();
+    }
+    // `first` may have become 10^block, one digit too many
+    if first > last || first.to_string().len() > block {
+        return BigTally::default();
+    }
+
+    // 10101 for 6 digits in blocks of 2
+    let multiplier = parse(&format!("{:0>width$}", "1", width = block).repeat(repeats));
+    let count = last.clone() - first.clone() + one;
+    let sum = (multiplier * (first + last) * count.clone()).half();
+    BigTally { count, sum }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IdIssue, IdRange, tally};
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn big_uint_test() {
+        let a = big("123456789012345678901234567890");
+        let b = big("987654321098765432109876543210");
+        assert_eq!(
+            "1111111110111111111011111111100",
+            (a.clone() + b.clone()).to_string()
+        );
+        assert_eq!(
+            "864197532086419753208641975320",
+            (b.clone() - a.clone()).to_string()
+        );
+        assert_eq!(None, a.checked_sub(&b));
+        assert_eq!(
+            "121932631137021795226185032733622923332237463801111263526900",
+            (a.clone() * b).to_string()
+        );
+        assert_eq!("61728394506172839450617283945", a.half().to_string());
+        assert_eq!("0", big("000").to_string());
+        assert_eq!(big("1000000000"), BigUint::from(1_000_000_000));
+        assert_eq!(Some(u128::MAX), BigUint::from(u128::MAX).to_u128());
+        assert_eq!(
+            None,
+            (BigUint::from(u128::MAX) + BigUint::from(1)).to_u128()
+        );
+        assert!(big("999999999") < big("1000000000"));
+        assert!("12a".parse::<BigUint>().is_err());
+    }
+
+    #[test]
+    fn matches_u64_tally_test() {
+        let ranges = [
+            (0, 10_000),
+            (95, 115),
+            (998, 1012),
+            (99_990, 1_000_100),
+            (0, u64::MAX),
+        ];
+        for (start, end) in ranges {
+            for policy in [
+                IdPolicy::TWICE,
+                IdPolicy::AT_LEAST_TWICE,
+                IdPolicy::Exactly(3),
+            ] {
+                let range = BigRange::from(IdRange::new(start, end));
+                assert_eq!(
+                    Ok(tally(IdRange::new(start, end), policy)),
+                    range.tally(policy).unwrap().to_tally(),
+                    "{}-{} {}",
+                    start,
+                    end,
+                    policy
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn beyond_u64_test() {
+        // 40 digits, both ends a 20 digit block twice
+        let (start, end) = (
+            "1234567890123456789012345678901234567890",
+            "1234567890123456789112345678901234567891",
+        );
+        let range: BigRange = format!("{}-{}", start, end).parse().unwrap();
+        let tally = range.tally(IdPolicy::TWICE).unwrap();
+        assert_eq!(big("2"), tally.count);
+        assert_eq!(big(start) + big(end), tally.sum);
+        assert_eq!(
+            Err(Overflow {
+                value: tally.sum.clone(),
+                limit: "u128"
+            }),
+            tally.to_tally()
+        );
+        assert_eq!(
+            Err(NoClosedForm(IdPolicy::Palindrome)),
+            range.tally(IdPolicy::Palindrome)
+        );
+
+        // every two digit block twice, 1010 to 9999, plus 2 digits: 11 to 99
+        let range: BigRange = "0-9999".parse().unwrap();
+        let tally = range.tally(IdPolicy::TWICE).unwrap();
+        assert_eq!(big("99"), tally.count);
+    }
+
+    #[test]
+    fn parse_big_range_test() {
+        assert!("22-11".parse::<BigRange>().is_err());
+        assert!("11-22".parse::<IdRange>().is_ok() && "11-22".parse::<BigRange>().is_ok());
+        assert_eq!(
+            "range 100000000000000000001-100000000000000000000 ends before it starts",
+            "100000000000000000001-100000000000000000000"
+                .parse::<BigRange>()
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "range has no end",
+            "12-".parse::<BigRange>().unwrap_err().to_string()
+        );
+        // the same rules for how IDs are written as for u64 ranges
+        for (text, issue) in [
+            ("0011-0022", IdIssue::LeadingZero),
+            ("+11-22", IdIssue::Sign('+')),
+            ("11-0100000000000000000000", IdIssue::LeadingZero),
+        ] {
+            assert!(
+                matches!(
+                    text.parse::<BigRange>(),
+                    Err(ParseRangeError::NotCanonical(found)) if found == issue
+                ),
+                "{}",
+                text
+            );
+        }
+        assert!(matches!(
+            "1x-22".parse::<BigRange>(),
+            Err(ParseRangeError::NotInteger)
+        ));
+        assert!(crate::parse_big_range_list("  11 - 22 ,").is_ok());
+    }
+
+    #[test]
+    fn ranges_test() {
+        let max = u128::from(u64::MAX);
+        let ranges = Ranges::new(vec![
+            "5-18446744073709551620".parse().unwrap(),
+            "100000000000000000000-100000000000000000010"
+                .parse()
+                .unwrap(),
+            "18446744073709551617-18446744073709551630".parse().unwrap(),
+            "7-9".parse().unwrap(),
+        ]);
+        assert_eq!(&[IdRange::new(5, u64::MAX)], ranges.ids.ranges());
+        assert_eq!(
+            vec![
+                BigRange {
+                    start: BigUint::from(max + 1),
+                    end: BigUint::from(max + 15)
+                },
+                "100000000000000000000-100000000000000000010"
+                    .parse()
+                    .unwrap()
+            ],
+            ranges.big
+        );
+        // everything up to u64::MAX, then one more twenty digit ID
+        let all = tally(IdRange::new(0, u64::MAX), IdPolicy::TWICE).sum;
+        let ranges = Ranges::new(vec!["0-18446744081844674408".parse().unwrap()]);
+        assert_eq!(
+            BigUint::from(all) + big("18446744081844674408"),
+            ranges.sum_invalid(IdPolicy::TWICE).unwrap()
+        );
+    }
+}
@@ -0,0 +1,261 @@
+// Counting and summing invalid IDs without looking at every number.
+//
+// A number of L digits made of a k digit block repeated L/k times is the
+// block times a multiplier of ones spaced k apart, e.g. for L = 6, k = 2:
+//   121212 = 12 * 10101, and 10101 = (10^6 - 1) / (10^2 - 1).
+// So within one digit length the candidates for a given k are just the
+// blocks B with B * M inside the range: an interval of B, whose count and sum
+// are closed formulas.
+//
+// Different k overlap: 111111 is 1 six times, 11 three times and 111 twice.
+// A number's smallest block length (its period) is unique though, so the
+// numbers are counted by period, from the smallest up, taking away from each
+// block length what its divisors have already counted. A policy then decides
+// which periods make an ID invalid.
+
+use crate::policy::multiplier;
+use crate::{IdPolicy, IdRange, InvalidIds};
+use std::ops::{Add, AddAssign, Sub};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub count: u64,
+    // u128: summing many IDs close to u64::MAX overflows u64
+    pub sum: u128,
+}
+
+impl Tally {
+    pub fn checked_add(self, other: Tally) -> Option<Tally> {
+        Some(Tally {
+            count: self.count.checked_add(other.count)?,
+            sum: self.sum.checked_add(other.sum)?,
+        })
+    }
+}
+
+// Tallies of u64 ranges can't overflow: even all of the u64 numbers together
+// sum to about 1.7 * 10^38, below u128::MAX. Anything bigger belongs in a
+// BigTally; this panics rather than wrap if that is ever not the case.
+impl Add for Tally {
+    type Output = Tally;
+
+    fn add(self, other: Tally) -> Tally {
+        self.checked_add(other)
+            .expect("tally overflowed, use BigRange")
+    }
+}
+
+impl Sub for Tally {
+    type Output = Tally;
+
+    fn sub(self, other: Tally) -> Tally {
+        Tally {
+            count: self.count - other.count,
+            sum: self.sum - other.sum,
+        }
+    }
+}
+
+impl AddAssign for Tally {
+    fn add_assign(&mut self, other: Tally) {
+        *self = *self + other;
+    }
+}
+
+// u64 numbers have at most 20 digits
+const MAX_DIGITS: u32 = 20;
+
+pub(crate) fn pow10(exp: u32) -> u128 {
+    10u128.pow(exp)
+}
+
+// every invalid ID in `range`, in O(digits * divisors) instead of O(end - start)
+// for the repeated block policies; others go through `InvalidIds`
+pub fn tally(range: IdRange, policy: IdPolicy) -> Tally {
+    if policy.allows_repeats(2).is_none() {
+        return InvalidIds::new(range, policy).fold(Tally::default(), |tally, id| {
+            tally
+                + Tally {
+                    count: 1,
+                    sum: u128::from(id),
+                }
+        });
+    }
+
+    let mut total = Tally::default();
+    for digits in 1..=MAX_DIGITS {
+        // the part of the range with exactly `digits` digits
+        let low = u128::from(range.start).max(pow10(digits - 1));
+        let high = u128::from(range.end).min(pow10(digits) - 1);
+        if low > high {
+            continue;
+        }
+
+        total += invalid_of_length(digits, policy, |block| repeated(low, high, digits, block));
+    }
+    total
+}
+
+// The invalid numbers of one digit length, given the numbers made of each
+// block length (for any kind of count and sum, so the big number mode can use
+// it too). `policy` must be a repeated block policy.
+pub(crate) fn invalid_of_length<T>(digits: u32, policy: IdPolicy, repeated: impl Fn(u32) -> T) -> T
+where
+    T: Clone + Default + Add<Output = T> + Sub<Output = T>,
+{
+    // the whole number is a block too, so that every number has a period,
+    // though a block once is never invalid
+    let blocks: Vec<u32> = (1..=digits).filter(|&k| digits.is_multiple_of(k)).collect();
+    // numbers whose smallest block has length blocks[i]
+    let mut by_period: Vec<T> = Vec::with_capacity(blocks.len());
+    for (i, &block) in blocks.iter().enumerate() {
+        let mut exact = repeated(block);
+        for (j, &smaller) in blocks[..i].iter().enumerate() {
+            if block % smaller == 0 {
+                exact = exact - by_period[j].clone();
+            }
+        }
+        by_period.push(exact);
+    }
+
+    // a number with period p is also made of every block length that
+    // p divides, so it is invalid if any of those repeat counts is
+    let mut total = T::default();
+    for (i, &period) in blocks.iter().enumerate() {
+        let invalid = blocks.iter().any(|&block| {
+            block % period == 0 && policy.allows_repeats(digits / block) == Some(true)
+        });
+        if invalid {
+            total = total + by_period[i].clone();
+        }
+    }
+    total
+}
+
+// `digits` long numbers in low..=high that are a `block` digit block repeated
+fn repeated(low: u128, high: u128, digits: u32, block: u32) -> Tally {
+    let multiplier = multiplier(digits, block);
+    // blocks don't start with 0, or the number would be shorter
+    let first = pow10(block - 1).max(low.div_ceil(multiplier));
+    let last = (pow10(block) - 1).min(high / multiplier);
+    if first > last {
+        return Tally::default();
+    }
+    let count = last - first + 1;
+    Tally {
+        count: count as u64,
+        sum: multiplier * (first + last) * count / 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    fn brute(range: IdRange, policy: IdPolicy) -> Tally {
+        let ids = match policy {
+            IdPolicy::Exactly(2) => part1::collect_invalid_ids_for_range(range.start, range.end),
+            IdPolicy::AtLeast(2) => part2::collect_invalid_ids_for_range(range.start, range.end),
+            _ => (range.start..=range.end)
+                .filter(|&id| policy.is_invalid(id))
+                .collect(),
+        };
+        Tally {
+            count: ids.len() as u64,
+            sum: ids.iter().map(|&id| u128::from(id)).sum(),
+        }
+    }
+
+    #[test]
+    fn repeated_test() {
+        // 1212, 1313, ..., 9999 and 1010, 1111
+        assert_eq!(
+            Tally {
+                count: 2,
+                sum: 1010 + 1111
+            },
+            repeated(1000, 1200, 4, 2)
+        );
+        assert_eq!(
+            Tally {
+                count: 1,
+                sum: 111111
+            },
+            repeated(111110, 111112, 6, 1)
+        );
+    }
+
+    #[test]
+    fn overlapping_blocks_test() {
+        let range = IdRange::new(111111, 111111);
+        assert_eq!(
+            Tally {
+                count: 1,
+                sum: 111111
+            },
+            tally(range, IdPolicy::TWICE)
+        );
+        assert_eq!(
+            Tally {
+                count: 1,
+                sum: 111111
+            },
+            tally(range, IdPolicy::AT_LEAST_TWICE)
+        );
+        // 111 is three ones, not two of anything
+        let range = IdRange::new(111, 111);
+        assert_eq!(Tally::default(), tally(range, IdPolicy::TWICE));
+        assert_eq!(1, tally(range, IdPolicy::AT_LEAST_TWICE).count);
+    }
+
+    #[test]
+    fn matches_brute_force_test() {
+        let ranges = [
+            (0, 0),
+            (0, 20),
+            (0, 10_000),
+            (1, 10_000),
+            (95, 115),
+            (998, 1012),
+            (222220, 222224),
+            (1188511880, 1188511890),
+            (2121212118, 2121212124),
+            (99_990, 200_100),
+            (999_990, 1_000_100),
+        ];
+        for (start, end) in ranges {
+            let range = IdRange::new(start, end);
+            for policy in [
+                IdPolicy::TWICE,
+                IdPolicy::AT_LEAST_TWICE,
+                IdPolicy::Exactly(3),
+                IdPolicy::Exactly(1),
+                IdPolicy::AtLeast(0),
+                IdPolicy::AtLeast(1),
+                IdPolicy::Palindrome,
+            ] {
+                assert_eq!(
+                    brute(range, policy),
+                    tally(range, policy),
+                    "{} {:?}",
+                    range,
+                    policy
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn wide_range_test() {
+        // 9 two digit, 90 four digit, ... up to 18 digits, and then the
+        // twenty digit ones that fit in a u64
+        let everything = IdRange::new(0, u64::MAX);
+        let twice = tally(everything, IdPolicy::TWICE);
+        let expected: u64 = (1..=9).map(|k| 9 * 10u64.pow(k - 1)).sum::<u64>()
+            // the largest is 1844674407 twice, just below 18446744073709551615
+            + (1844674407 - 1000000000 + 1);
+        assert_eq!(expected, twice.count);
+        assert!(tally(everything, IdPolicy::AT_LEAST_TWICE).count > twice.count);
+    }
+}
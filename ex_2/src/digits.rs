@@ -0,0 +1,101 @@
+// Digit math on the numbers themselves instead of on their text: counting
+// digits, cutting a number into blocks at powers of ten and gluing a block
+// back together. No allocation, so checking every number of a range stays
+// cheap (see benches/digits.rs for how much).
+
+use crate::policy::multiplier;
+
+// 1 for 0..=9, 20 for u64::MAX
+pub fn count(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+// the number split before its last `low` digits: (1234, 1) is (123, 4)
+pub fn split(n: u64, low: u32) -> (u64, u64) {
+    match 10u64.checked_pow(low) {
+        Some(power) => (n / power, n % power),
+        None => (0, n),
+    }
+}
+
+// the left and right half, for an even number of digits
+pub fn halves(n: u64) -> (u64, u64) {
+    split(n, count(n) / 2)
+}
+
+// `block` written `times` times in a row, if that fits: (12, 3) is 121212
+pub fn repeat(block: u64, times: u32) -> Option<u64> {
+    let shift = 10u64.checked_pow(count(block))?;
+    (1..times).try_fold(block, |n, _| n.checked_mul(shift)?.checked_add(block))
+}
+
+// The `len` digit blocks of `n` from the left; a shorter leftover block
+// comes first if `len` doesn't divide the digit count: (12345, 2) is 1, 23, 45
+pub fn blocks(n: u64, len: u32) -> impl Iterator<Item = u64> {
+    let digits = count(n);
+    let size = 10u64.checked_pow(len);
+    (0..digits.div_ceil(len.max(1))).rev().map(move |i| {
+        let (high, _) = split(n, i * len);
+        size.map_or(high, |size| high % size)
+    })
+}
+
+// Is `n` a `len` digit block repeated, at least once. Rather than comparing
+// the blocks one by one: a number made of repeated blocks is the block times
+// 1..01..01, and the other way round (see closed_form.rs).
+pub fn is_repeat(n: u64, len: u32) -> bool {
+    let digits = count(n);
+    if len == 0 || !digits.is_multiple_of(len) {
+        return false;
+    }
+    match 10u64.checked_pow(digits) {
+        Some(power) => n.is_multiple_of((power - 1) / (10u64.pow(len) - 1)),
+        // 20 digits, u128 is much slower so only here
+        None => u128::from(n).is_multiple_of(multiplier(digits, len)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_test() {
+        assert_eq!(1, count(0));
+        assert_eq!(1, count(9));
+        assert_eq!(2, count(10));
+        assert_eq!(20, count(u64::MAX));
+    }
+
+    #[test]
+    fn split_test() {
+        assert_eq!((123, 4), split(1234, 1));
+        assert_eq!((0, 1234), split(1234, 4));
+        assert_eq!((12, 34), halves(1234));
+        assert_eq!((10, 1), halves(101));
+        assert_eq!((1844674407, 3709551615), halves(u64::MAX));
+        assert_eq!((0, u64::MAX), split(u64::MAX, 20));
+    }
+
+    #[test]
+    fn repeat_test() {
+        assert_eq!(Some(121212), repeat(12, 3));
+        assert_eq!(Some(1010), repeat(10, 2));
+        assert_eq!(Some(7), repeat(7, 1));
+        assert_eq!(Some(18446744071844674407), repeat(1844674407, 2));
+        assert_eq!(None, repeat(1844674408, 2));
+    }
+
+    #[test]
+    fn blocks_test() {
+        assert_eq!(vec![1, 23, 45], blocks(12345, 2).collect::<Vec<_>>());
+        assert_eq!(vec![10, 2, 0], blocks(100200, 2).collect::<Vec<_>>());
+        assert_eq!(vec![u64::MAX], blocks(u64::MAX, 20).collect::<Vec<_>>());
+        assert!(is_repeat(112112, 3));
+        assert!(is_repeat(111, 1));
+        assert!(is_repeat(7, 1));
+        assert!(!is_repeat(112112, 2));
+        assert!(!is_repeat(1111112, 3));
+        assert!(!is_repeat(1010, 0));
+    }
+}
@@ -0,0 +1,223 @@
+// Why an ID is invalid: every way it splits into a repeated block, and the
+// one the policy objects to. 111111 is 1×6, 11×3 and 111×2; part 1 only
+// cares about the last, part 2 about any of them and reports the longest
+// block, like its `has_a_pattern` which tries the longest first.
+
+use crate::{IdPolicy, IdRange, InvalidIds, RangeSet, digits};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decomposition {
+    pub block: u64,
+    pub repeats: u32,
+}
+
+impl fmt::Display for Decomposition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}×{}", self.block, self.repeats)
+    }
+}
+
+impl Decomposition {
+    // all of `id`'s repeated blocks, shortest block first; the ID itself
+    // once (×1) is left out
+    pub fn all(id: u64) -> Vec<Decomposition> {
+        let count = digits::count(id);
+        (1..count)
+            .filter(|&len| digits::is_repeat(id, len))
+            .map(|len| Decomposition {
+                block: digits::split(id, count - len).0,
+                repeats: count / len,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub id: u64,
+    // the input range the ID came from, the first one if several overlap
+    pub range: IdRange,
+    // the decomposition that makes the ID invalid, None for policies that
+    // aren't about repeated blocks
+    pub because: Option<Decomposition>,
+    pub decompositions: Vec<Decomposition>,
+}
+
+impl Explanation {
+    pub fn new(id: u64, range: IdRange, policy: IdPolicy) -> Explanation {
+        let decompositions = Decomposition::all(id);
+        let whole = Decomposition {
+            block: id,
+            repeats: 1,
+        };
+        let because = decompositions
+            .iter()
+            .chain([&whole])
+            .filter(|decomposition| policy.allows_repeats(decomposition.repeats) == Some(true))
+            .min_by_key(|decomposition| decomposition.repeats)
+            .copied();
+        Explanation {
+            id,
+            range,
+            because,
+            decompositions,
+        }
+    }
+
+    pub const TABLE_HEADER: &str =
+        "range                      id                    block       repeats  decompositions";
+
+    pub fn to_row(&self) -> String {
+        let (block, repeats) = match self.because {
+            Some(because) => (because.block.to_string(), because.repeats.to_string()),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        let decompositions: Vec<String> = self
+            .decompositions
+            .iter()
+            .map(|decomposition| decomposition.to_string())
+            .collect();
+        format!(
+            "{:<26} {:<21} {:<11} {:<8} {}",
+            self.range.to_string(),
+            self.id,
+            block,
+            repeats,
+            decompositions.join(" = ")
+        )
+    }
+
+    // one JSON object; the block is a string like in the puzzle text
+    pub fn to_json(&self) -> String {
+        let because = match self.because {
+            Some(because) => format!(
+                "\"block\":\"{}\",\"repeats\":{}",
+                because.block, because.repeats
+            ),
+            None => "\"block\":null,\"repeats\":null".to_string(),
+        };
+        let decompositions: Vec<String> = self
+            .decompositions
+            .iter()
+            .map(|decomposition| {
+                format!(
+                    "{{\"block\":\"{}\",\"repeats\":{}}}",
+                    decomposition.block, decomposition.repeats
+                )
+            })
+            .collect();
+        format!(
+            "{{\"id\":{},\"range\":\"{}\",{},\"decompositions\":[{}]}}",
+            self.id,
+            self.range,
+            because,
+            decompositions.join(",")
+        )
+    }
+}
+
+// Every invalid ID of the input `ranges` with its reason, in ascending order
+// and each once, even if it is in overlapping ranges. Like a `Report`, an
+// inverted range has nothing to explain.
+pub fn explain(ranges: &[IdRange], policy: IdPolicy) -> impl Iterator<Item = Explanation> + '_ {
+    let merged = RangeSet::new(ranges.to_vec()).unwrap_or_default();
+    merged
+        .ranges()
+        .to_vec()
+        .into_iter()
+        .flat_map(move |range| InvalidIds::new(range, policy))
+        .map(move |id| {
+            let range = ranges
+                .iter()
+                .find(|range| (range.start..=range.end).contains(&id))
+                .expect("the merged ranges are made of the input ones");
+            Explanation::new(id, *range, policy)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decomposition(block: u64, repeats: u32) -> Decomposition {
+        Decomposition { block, repeats }
+    }
+
+    #[test]
+    fn decompositions_test() {
+        assert_eq!(
+            vec![
+                decomposition(1, 6),
+                decomposition(11, 3),
+                decomposition(111, 2)
+            ],
+            Decomposition::all(111111)
+        );
+        assert_eq!(vec![decomposition(12, 3)], Decomposition::all(121212));
+        assert!(Decomposition::all(1221).is_empty());
+        assert!(Decomposition::all(7).is_empty());
+    }
+
+    #[test]
+    fn explain_test() {
+        let ranges = crate::parse_range_list("95-115,222220-222224").unwrap();
+        let twice: Vec<(u64, Option<Decomposition>)> = explain(&ranges, IdPolicy::TWICE)
+            .map(|explanation| (explanation.id, explanation.because))
+            .collect();
+        assert_eq!(
+            vec![
+                (99, Some(decomposition(9, 2))),
+                (222222, Some(decomposition(222, 2)))
+            ],
+            twice
+        );
+        let any: Vec<Explanation> = explain(&ranges, IdPolicy::AT_LEAST_TWICE).collect();
+        assert_eq!(3, any.len());
+        // 111 is only invalid for part 2
+        assert_eq!(Some(decomposition(1, 3)), any[1].because);
+        assert_eq!(IdRange::new(95, 115), any[1].range);
+        // the ranges as given, not as merged
+        let ranges = crate::parse_range_list("11-22,15-33,30-44").unwrap();
+        let from: Vec<(u64, String)> = explain(&ranges, IdPolicy::TWICE)
+            .map(|explanation| (explanation.id, explanation.range.to_string()))
+            .collect();
+        assert_eq!(
+            vec![
+                (11, "11-22".to_string()),
+                (22, "11-22".to_string()),
+                (33, "15-33".to_string()),
+                (44, "30-44".to_string()),
+            ],
+            from
+        );
+        // whichever repeat count the policy wants
+        assert_eq!(
+            Some(decomposition(2, 6)),
+            Explanation::new(222222, any[2].range, IdPolicy::Exactly(6)).because
+        );
+
+        let palindrome = Explanation::new(1221, IdRange::new(0, 2000), IdPolicy::Palindrome);
+        assert_eq!(None, palindrome.because);
+    }
+
+    #[test]
+    fn render_test() {
+        let range = IdRange::new(111110, 111112);
+        let explanation = Explanation::new(111111, range, IdPolicy::AT_LEAST_TWICE);
+        assert_eq!(
+            "111110-111112              111111                111         2        1×6 = 11×3 = 111×2",
+            explanation.to_row()
+        );
+        assert_eq!(
+            r#"{"id":111111,"range":"111110-111112","block":"111","repeats":2,"decompositions":[{"block":"1","repeats":6},{"block":"11","repeats":3},{"block":"111","repeats":2}]}"#,
+            explanation.to_json()
+        );
+        let palindrome = Explanation::new(1221, range, IdPolicy::Palindrome);
+        assert!(
+            palindrome
+                .to_json()
+                .contains(r#""block":null,"repeats":null"#)
+        );
+    }
+}
@@ -0,0 +1,175 @@
+// An ID as the puzzle means it: digits only, no sign, no surrounding
+// whitespace and no leading zeros ("0101 isn't an ID at all"), just 0 itself.
+// `u64::from_str` happily takes `+101` or `0101`, and a digit count taken
+// from that text then disagrees with the number, so IDs are read through here.
+
+use crate::digits;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Id(pub u64);
+
+// what is wrong with a would-be ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdIssue {
+    Empty,
+    Whitespace,
+    Sign(char),
+    LeadingZero,
+    NotDigit(char),
+    TooLarge,
+}
+
+impl IdIssue {
+    // `Id::normalize` can fix these and carry on
+    pub fn is_fixable(&self) -> bool {
+        matches!(
+            self,
+            IdIssue::Whitespace | IdIssue::Sign('+') | IdIssue::LeadingZero
+        )
+    }
+}
+
+impl fmt::Display for IdIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdIssue::Empty => write!(f, "ID is empty"),
+            IdIssue::Whitespace => write!(f, "ID has whitespace around it"),
+            IdIssue::Sign(sign) => write!(f, "ID has a `{}` sign", sign),
+            IdIssue::LeadingZero => write!(f, "ID has a leading zero"),
+            IdIssue::NotDigit(found) => write!(f, "`{}` is not a digit", found),
+            IdIssue::TooLarge => write!(f, "ID does not fit in 64 bits"),
+        }
+    }
+}
+
+impl Error for IdIssue {}
+
+impl Id {
+    pub fn digits(&self) -> u32 {
+        digits::count(self.0)
+    }
+
+    // `canonical`, and whether what is left fits in a u64
+    fn check(s: &str) -> (Vec<IdIssue>, &str) {
+        let (mut issues, rest) = canonical(s);
+        if issues.iter().all(IdIssue::is_fixable) && rest.parse::<u64>().is_err() {
+            issues.push(IdIssue::TooLarge);
+        }
+        (issues, rest)
+    }
+
+    // Lenient reading: whitespace, a `+` and leading zeros are dropped, and
+    // returned so the caller can say what was changed. Anything else, a `-`
+    // included, is still an error.
+    pub fn normalize(s: &str) -> Result<(Id, Vec<IdIssue>), IdIssue> {
+        let (issues, rest) = Id::check(s);
+        if let Some(issue) = issues.iter().find(|issue| !issue.is_fixable()) {
+            return Err(*issue);
+        }
+        let id = rest.parse().expect("checked above");
+        Ok((Id(id), issues))
+    }
+}
+
+// Every problem with `s` as an ID of any size, in the order they are fixed:
+// whitespace, then sign, then leading zeros, then whatever is left. Also
+// what is left, for `BigRange` which has no 64 bit limit.
+pub(crate) fn canonical(s: &str) -> (Vec<IdIssue>, &str) {
+    let mut issues = vec![];
+    let mut rest = s.trim();
+    if rest.len() != s.len() {
+        issues.push(IdIssue::Whitespace);
+    }
+    if let Some(sign) = rest.chars().next().filter(|c| *c == '+' || *c == '-') {
+        issues.push(IdIssue::Sign(sign));
+        rest = &rest[1..];
+    }
+    let digits = rest.trim_start_matches('0');
+    if rest.len() > 1 && digits.len() < rest.len() {
+        issues.push(IdIssue::LeadingZero);
+        rest = if digits.is_empty() { "0" } else { digits };
+    }
+    if rest.is_empty() {
+        issues.push(IdIssue::Empty);
+    } else if let Some(found) = rest.chars().find(|c| !c.is_ascii_digit()) {
+        issues.push(IdIssue::NotDigit(found));
+    }
+    (issues, rest)
+}
+
+// strict reading: the text has to be the ID exactly as it would be printed
+impl FromStr for Id {
+    type Err = IdIssue;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (issues, rest) = Id::check(s);
+        match issues.first() {
+            Some(issue) => Err(*issue),
+            None => Ok(Id(rest.parse().expect("checked above"))),
+        }
+    }
+}
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_test() {
+        assert_eq!(Ok(Id(101)), "101".parse());
+        assert_eq!(Ok(Id(0)), "0".parse());
+        assert_eq!(Ok(Id(u64::MAX)), "18446744073709551615".parse());
+        let cases = [
+            ("", IdIssue::Empty),
+            (" 101", IdIssue::Whitespace),
+            ("+101", IdIssue::Sign('+')),
+            ("-101", IdIssue::Sign('-')),
+            ("0101", IdIssue::LeadingZero),
+            ("00", IdIssue::LeadingZero),
+            ("1o1", IdIssue::NotDigit('o')),
+            ("18446744073709551616", IdIssue::TooLarge),
+        ];
+        for (text, issue) in cases {
+            assert_eq!(Err(issue), text.parse::<Id>(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn normalize_test() {
+        assert_eq!(Ok((Id(101), vec![])), Id::normalize("101"));
+        assert_eq!(
+            Ok((
+                Id(101),
+                vec![
+                    IdIssue::Whitespace,
+                    IdIssue::Sign('+'),
+                    IdIssue::LeadingZero
+                ]
+            )),
+            Id::normalize(" +0101\t")
+        );
+        assert_eq!(
+            Ok((Id(0), vec![IdIssue::LeadingZero])),
+            Id::normalize("000")
+        );
+        assert_eq!(Err(IdIssue::Sign('-')), Id::normalize("-5"));
+        assert_eq!(Err(IdIssue::Empty), Id::normalize(" + "));
+        assert_eq!(Err(IdIssue::NotDigit('x')), Id::normalize("0x1"));
+    }
+
+    #[test]
+    fn digits_test() {
+        assert_eq!(1, Id(0).digits());
+        assert_eq!(4, Id(1010).digits());
+        assert_eq!(20, Id(u64::MAX).digits());
+    }
+}
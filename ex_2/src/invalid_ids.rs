@@ -0,0 +1,213 @@
+// The invalid IDs of a range one by one, in ascending order. For repeated
+// blocks this works like part 1's `next_posible_patterned_number`, for any
+// number of repeats: from a number, the next candidate for each block length
+// is the smallest block B with B * multiplier not below it, and the next
+// invalid ID is the least of those. Palindromes are jumped through by
+// mirroring the left half; other policies check every number.
+
+use crate::closed_form::pow10;
+use crate::policy::multiplier;
+use crate::{IdPolicy, IdRange};
+
+#[derive(Debug, Clone)]
+pub struct InvalidIds {
+    policy: IdPolicy,
+    // the next number that could be an invalid ID; None once past the end
+    from: Option<u128>,
+    end: u128,
+}
+
+impl InvalidIds {
+    pub fn new(range: IdRange, policy: IdPolicy) -> InvalidIds {
+        InvalidIds {
+            policy,
+            from: Some(u128::from(range.start)),
+            end: u128::from(range.end),
+        }
+    }
+}
+
+impl Iterator for InvalidIds {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let from = self.from?;
+        let next = match self.policy {
+            IdPolicy::Exactly(_) | IdPolicy::AtLeast(_) => next_repeated(from, self.policy),
+            IdPolicy::Palindrome => Some(next_palindrome(from)),
+            _ => (from..=self.end).find(|&id| self.policy.is_invalid(id as u64)),
+        };
+        let next = next.filter(|&next| next <= self.end);
+        self.from = next.map(|next| next + 1);
+        next.map(|next| next as u64)
+    }
+}
+
+// the smallest repeated block ID >= from, if it fits in a u64
+fn next_repeated(from: u128, policy: IdPolicy) -> Option<u128> {
+    let mut from = from;
+    let mut digits = from.max(1).ilog10() + 1;
+    while digits <= 20 {
+        let next = (1..=digits)
+            .filter(|&block| {
+                digits.is_multiple_of(block) && policy.allows_repeats(digits / block) == Some(true)
+            })
+            .filter_map(|block| {
+                let multiplier = multiplier(digits, block);
+                let smallest = pow10(block - 1).max(from.div_ceil(multiplier));
+                (smallest < pow10(block)).then_some(smallest * multiplier)
+            })
+            .min();
+        if next.is_some() {
+            return next.filter(|&next| next <= u128::from(u64::MAX));
+        }
+        // nothing left with this many digits
+        from = pow10(digits);
+        digits += 1;
+    }
+    None
+}
+
+// the smallest palindrome of two or more digits >= from
+fn next_palindrome(from: u128) -> u128 {
+    let from = from.max(11);
+    let digits = from.ilog10() + 1;
+    let half = digits.div_ceil(2);
+    let left = from / pow10(digits - half);
+    let candidate = mirror(left, digits);
+    if candidate >= from {
+        candidate
+    } else if left + 1 < pow10(half) {
+        mirror(left + 1, digits)
+    } else {
+        // from 99..9 to 100..01
+        pow10(digits) + 1
+    }
+}
+
+// the `digits` long palindrome starting with `left`
+fn mirror(left: u128, digits: u32) -> u128 {
+    let mut palindrome = left;
+    let mut rest = if digits % 2 == 1 { left / 10 } else { left };
+    while rest > 0 {
+        palindrome = palindrome * 10 + rest % 10;
+        rest /= 10;
+    }
+    palindrome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, tally};
+
+    #[test]
+    fn invalid_ids_test() {
+        let twice: Vec<u64> = InvalidIds::new(IdRange::new(95, 1012), IdPolicy::TWICE).collect();
+        assert_eq!(vec![99, 1010], twice);
+        let any: Vec<u64> =
+            InvalidIds::new(IdRange::new(95, 1012), IdPolicy::AT_LEAST_TWICE).collect();
+        assert_eq!(
+            vec![99, 111, 222, 333, 444, 555, 666, 777, 888, 999, 1010],
+            any
+        );
+        // 111111 is both 111 twice and 1 six times, but comes once
+        let ids: Vec<u64> =
+            InvalidIds::new(IdRange::new(111110, 111112), IdPolicy::AT_LEAST_TWICE).collect();
+        assert_eq!(vec![111111], ids);
+        assert_eq!(
+            0,
+            InvalidIds::new(IdRange::new(0, 10), IdPolicy::AT_LEAST_TWICE).count()
+        );
+    }
+
+    #[test]
+    fn other_policies_test() {
+        let palindromes: Vec<u64> =
+            InvalidIds::new(IdRange::new(0, 130), IdPolicy::Palindrome).collect();
+        assert_eq!(
+            vec![11, 22, 33, 44, 55, 66, 77, 88, 99, 101, 111, 121],
+            palindromes
+        );
+        let ids: Vec<u64> =
+            InvalidIds::new(IdRange::new(99_990, 1_000_100), IdPolicy::Palindrome).collect();
+        let brute: Vec<u64> = (99_990..=1_000_100)
+            .filter(|&id| IdPolicy::Palindrome.is_invalid(id))
+            .collect();
+        assert_eq!(brute, ids);
+        let last = InvalidIds::new(
+            IdRange::new(u64::MAX - 10_000_000_000, u64::MAX),
+            IdPolicy::Palindrome,
+        );
+        assert_eq!(Some(18446744066044764481), last.last());
+
+        let zeros: Vec<u64> = InvalidIds::new(IdRange::new(1000, 1100), IdPolicy::LeadingZeroBlock)
+            .take(4)
+            .collect();
+        assert_eq!(vec![1000, 1001, 1002, 1003], zeros);
+        let odd = IdPolicy::custom("odd", |id| id % 2 == 1);
+        assert_eq!(3, InvalidIds::new(IdRange::new(10, 15), odd).count());
+    }
+
+    #[test]
+    fn matches_brute_force_test() {
+        for (start, end) in [
+            (0, 10_000),
+            (1, 10_000),
+            (99_990, 1_000_100),
+            (2121212118, 2121212124),
+        ] {
+            assert_eq!(
+                part1::collect_invalid_ids_for_range(start, end),
+                InvalidIds::new(IdRange::new(start, end), IdPolicy::TWICE).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                part2::collect_invalid_ids_for_range(start, end),
+                InvalidIds::new(IdRange::new(start, end), IdPolicy::AT_LEAST_TWICE)
+                    .collect::<Vec<_>>()
+            );
+        }
+        // and with the policy asked about every number, 0 included
+        for policy in [
+            IdPolicy::Exactly(1),
+            IdPolicy::AtLeast(0),
+            IdPolicy::AtLeast(1),
+            IdPolicy::Exactly(3),
+        ] {
+            assert_eq!(
+                (0..=10_000)
+                    .filter(|&id| policy.is_invalid(id))
+                    .collect::<Vec<_>>(),
+                InvalidIds::new(IdRange::new(0, 10_000), policy).collect::<Vec<_>>(),
+                "{:?}",
+                policy
+            );
+        }
+    }
+
+    #[test]
+    fn wide_range_test() {
+        let everything = IdRange::new(0, u64::MAX);
+        let first: Vec<u64> = InvalidIds::new(everything, IdPolicy::TWICE)
+            .take(3)
+            .collect();
+        assert_eq!(vec![11, 22, 33], first);
+        // the last one fitting in a u64, then nothing
+        let mut ids = InvalidIds::new(
+            IdRange::new(18446744071844674407, u64::MAX),
+            IdPolicy::TWICE,
+        );
+        assert_eq!(Some(18446744071844674407), ids.next());
+        assert_eq!(None, ids.next());
+        assert_eq!(None, ids.next());
+
+        let wide = IdRange::new(1, 1_000_000_000);
+        for policy in [IdPolicy::TWICE, IdPolicy::AT_LEAST_TWICE] {
+            let ids = InvalidIds::new(wide, policy);
+            let (count, sum) =
+                ids.fold((0, 0), |(count, sum), id| (count + 1, sum + u128::from(id)));
+            let expected = tally(wide, policy);
+            assert_eq!((expected.count, expected.sum), (count, sum));
+        }
+    }
+}
@@ -0,0 +1,248 @@
+mod big;
+mod closed_form;
+pub mod digits;
+mod explain;
+mod id;
+mod invalid_ids;
+pub mod part1;
+pub mod part2;
+mod policy;
+mod range_list;
+mod range_set;
+mod report;
+
+pub use big::{BigRange, BigTally, BigUint, NoClosedForm, Overflow, ParseBigUintError, Ranges};
+pub use closed_form::{Tally, tally};
+pub use explain::{Decomposition, Explanation, explain};
+pub use id::{Id, IdIssue};
+pub use invalid_ids::InvalidIds;
+pub use policy::{IdPolicy, ParsePolicyError, Policies};
+pub use range_list::{parse_big_range_list, parse_range_list};
+pub use range_set::{InvertedRange, RangeSet};
+pub use report::{RangeReport, Report};
+
+use solution::Solution;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// one `start-end` entry of the comma separated input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl fmt::Display for IdRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseRangeError {
+    // nothing between two commas
+    Empty,
+    MissingDash,
+    // `-5` and `12-`
+    MissingStart,
+    MissingEnd,
+    NotInteger,
+    // digits, but too many for a u64; only the day 2 answers, which read
+    // the input as BigRanges, handle those
+    TooLarge,
+    // a number, but not written the way IDs are, like 0101 or +5
+    NotCanonical(IdIssue),
+    Inverted(IdRange),
+    BigInverted(BigRange),
+}
+
+impl fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseRangeError::Empty => write!(f, "range is empty"),
+            ParseRangeError::MissingDash => write!(f, "start and end must be separated by -"),
+            ParseRangeError::MissingStart => write!(f, "range has no start"),
+            ParseRangeError::MissingEnd => write!(f, "range has no end"),
+            ParseRangeError::NotInteger => write!(f, "start and end must be integers"),
+            ParseRangeError::TooLarge => {
+                write!(f, "start and end must be at most {}", u64::MAX)
+            }
+            ParseRangeError::NotCanonical(issue) => write!(f, "{}", issue),
+            ParseRangeError::Inverted(range) => write!(f, "{}", InvertedRange(*range)),
+            ParseRangeError::BigInverted(range) => {
+                write!(f, "range {} ends before it starts", range)
+            }
+        }
+    }
+}
+
+impl Error for ParseRangeError {}
+
+// what is wrong with one side of the dash
+impl From<IdIssue> for ParseRangeError {
+    fn from(issue: IdIssue) -> ParseRangeError {
+        match issue {
+            IdIssue::Empty | IdIssue::NotDigit(_) => ParseRangeError::NotInteger,
+            IdIssue::TooLarge => ParseRangeError::TooLarge,
+            issue => ParseRangeError::NotCanonical(issue),
+        }
+    }
+}
+
+impl FromStr for IdRange {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').ok_or(ParseRangeError::MissingDash)?;
+        IdRange::from_bounds(start, end)
+    }
+}
+
+impl IdRange {
+    // unchecked, like the struct literal; parsing is what rejects inverted
+    // ranges
+    pub const fn new(start: u64, end: u64) -> IdRange {
+        IdRange { start, end }
+    }
+
+    // the two sides of the dash, each exactly as an ID is written
+    pub(crate) fn from_bounds(start: &str, end: &str) -> Result<IdRange, ParseRangeError> {
+        let bound = |s: &str| {
+            s.parse::<Id>()
+                .map(|id| id.0)
+                .map_err(ParseRangeError::from)
+        };
+        match (start.is_empty(), end.is_empty()) {
+            (true, false) => return Err(ParseRangeError::MissingStart),
+            (false, true) => return Err(ParseRangeError::MissingEnd),
+            _ => {}
+        }
+        let range = IdRange {
+            start: bound(start)?,
+            end: bound(end)?,
+        };
+        if range.end < range.start {
+            return Err(ParseRangeError::Inverted(range));
+        }
+        Ok(range)
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const ROOT: &'static str = env!("CARGO_MANIFEST_DIR");
+    const INPUT: &'static str = "ranges_final";
+    const EXAMPLE: &'static str = "ranges_example";
+
+    // merged, so overlapping ranges don't count an ID twice; any size, IDs
+    // past u64::MAX are counted as BigUints
+    type Input = Ranges;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> file_read::Result<Self::Input> {
+        parse_big_range_list(input).map(Ranges::new)
+    }
+
+    // the range list parser carries on past bad ranges, so all of them
+    fn validate(input: &str) -> Vec<file_read::Error> {
+        range_list::scan(input, BigRange::from_bounds)
+            .into_iter()
+            .filter_map(Result::err)
+            .collect()
+    }
+
+    fn part1(ranges: &Self::Input) -> BigUint {
+        ranges
+            .sum_invalid(IdPolicy::TWICE)
+            .expect("repeated block policy")
+    }
+
+    fn part2(ranges: &Self::Input) -> BigUint {
+        ranges
+            .sum_invalid(IdPolicy::AT_LEAST_TWICE)
+            .expect("repeated block policy")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+824824821-824824827,2121212118-2121212124";
+
+    #[test]
+    fn parse_test() {
+        let ranges = Day2::parse("11-22,95-115,\n998-1012").unwrap();
+        assert_eq!(
+            &[
+                IdRange::new(11, 22),
+                IdRange::new(95, 115),
+                IdRange::new(998, 1012),
+            ],
+            ranges.ids.ranges()
+        );
+        assert_eq!(
+            "1: start and end must be separated by - at column 7 in `95`",
+            Day2::parse("11-22,95").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "2: start and end must be integers at column 1 in `11-x`",
+            Day2::parse("1-2\n11-x").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "1: range 22-11 ends before it starts at column 5 in `22-11`",
+            Day2::parse("1-2,22-11").unwrap_err().to_string()
+        );
+        // too big for a u64 is fine, the part past u64::MAX goes to big
+        let ranges = Day2::parse("1-18446744073709551616").unwrap();
+        assert_eq!(1, ranges.big.len());
+        assert_eq!(
+            "1: start and end must be at most 18446744073709551615 at column 1 \
+             in `1-18446744073709551616`",
+            "1-18446744073709551616"
+                .parse::<RangeSet>()
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn overlapping_ranges_test() {
+        // 11 and 22 are in both ranges but count once
+        let ranges = Day2::parse("11-22,11-33").unwrap();
+        assert_eq!(1, ranges.ids.overlaps().len());
+        assert_eq!(BigUint::from(11 + 22 + 33), Day2::part1(&ranges));
+        assert_eq!(BigUint::from(11 + 22 + 33), Day2::part2(&ranges));
+    }
+
+    #[test]
+    fn example_test() {
+        let ranges = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(BigUint::from(1227775554), Day2::part1(&ranges));
+        assert_eq!(BigUint::from(4174379265), Day2::part2(&ranges));
+    }
+
+    #[test]
+    fn beyond_u64_test() {
+        // 18446744081844674408 is 1844674408 twice, the first such ID past
+        // u64::MAX, and no other block repeats in there
+        let ranges = Day2::parse("11-22,\n18446744073709551610-18446744081844674408").unwrap();
+        assert_eq!("18446744081844674441", Day2::part1(&ranges).to_string());
+        assert_eq!("18446744081844674441", Day2::part2(&ranges).to_string());
+        // two 20 digit blocks twice, far beyond even u128
+        let ranges = Day2::parse(
+            "1234567890123456789012345678901234567890-1234567890123456789112345678901234567891",
+        )
+        .unwrap();
+        assert_eq!(
+            "2469135780246913578124691357802469135781",
+            Day2::part1(&ranges).to_string()
+        );
+    }
+}
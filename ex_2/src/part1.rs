@@ -0,0 +1,156 @@
+// ID ranges -, separated by ,
+// ie:
+// 11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
+// 1698522-1698528,446443-446449,38593856-38593862,565653-565659,
+// 824824821-824824827,2121212118-2121212124
+// invalid IDs: some sequence of digits repeated twice.
+// 55 (5 twice), 6464 (64 twice), and 123123 (123 twice)
+// invalid ID has leading zeroes; 0101 isn't an ID at all
+// 101 is valid
+//
+// 11-22 has two invalid IDs, 11 and 22.
+// 95-115 has one invalid ID, 99.
+// 998-1012 has one invalid ID, 1010.
+// 1188511880-1188511890 has one invalid ID, 1188511885.
+// 222220-222224 has one invalid ID, 222222.
+// 1698522-1698528 contains no invalid IDs.
+// 446443-446449 has one invalid ID, 446446.
+// 38593856-38593862 has one invalid ID, 38593859.
+// The rest of the ranges contain no invalid IDs.
+// Adding up all the invalid IDs in this example produces 1227775554.
+// What do you get if you add up all of the invalid IDs?
+
+use crate::Id;
+use crate::digits;
+
+pub fn collect_invalid_ids_for_range(start: u64, end: u64) -> Vec<u64> {
+    let mut invalid_ids: Vec<u64> = vec![];
+    let mut current_number = start;
+    if check_range(Id(start), Id(end)) {
+        while current_number <= end {
+            current_number = bump_to_even_digits(Id(current_number)).0;
+            if has_a_pattern(current_number) {
+                invalid_ids.push(current_number)
+            } else {
+                match current_number.checked_add(1) {
+                    Some(next) => current_number = next,
+                    // that was u64::MAX
+                    None => return invalid_ids,
+                }
+                continue;
+            }
+            match next_posible_patterned_number(current_number) {
+                Some(val) => current_number = val,
+                // the next one would not fit in a u64
+                None => return invalid_ids,
+            }
+            if current_number > end {
+                return invalid_ids;
+            }
+        }
+    }
+    invalid_ids
+}
+
+fn has_a_pattern(number: u64) -> bool {
+    digits::count(number).is_multiple_of(2) && digits::is_repeat(number, digits::count(number) / 2)
+}
+
+fn next_posible_patterned_number(current_number: u64) -> Option<u64> {
+    if !digits::count(current_number).is_multiple_of(2) {
+        return None;
+    };
+
+    let (current_pattern_part, _) = digits::halves(current_number);
+    digits::repeat(current_pattern_part + 1, 2)
+}
+
+// digit counts come from the numbers, never from how they were written
+fn bump_to_even_digits(n: Id) -> Id {
+    let digits = n.digits();
+    if digits.is_multiple_of(2) {
+        n
+    } else {
+        Id(10u64.pow(digits))
+    }
+}
+
+fn check_range(start: Id, end: Id) -> bool {
+    let (start, end) = (start.digits(), end.digits());
+    start.is_multiple_of(2) || end.is_multiple_of(2) || start != end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_posible_patterned_number_test() {
+        assert_eq!(Some(33), next_posible_patterned_number(22));
+        assert_eq!(Some(1212), next_posible_patterned_number(1122));
+        assert_eq!(Some(1010), next_posible_patterned_number(99));
+        assert_eq!(None, next_posible_patterned_number(112));
+        assert_eq!(None, next_posible_patterned_number(18446744071844674407));
+    }
+
+    #[test]
+    fn bump_to_even_digits_test() {
+        assert_eq!(Id(10), bump_to_even_digits(Id(9)));
+        assert_eq!(Id(49), bump_to_even_digits(Id(49)));
+        assert_eq!(Id(1000), bump_to_even_digits(Id(495)));
+        assert_eq!(Id(4958), bump_to_even_digits(Id(4958)));
+        assert_eq!(Id(100000), bump_to_even_digits(Id(49586)));
+    }
+
+    #[test]
+    fn check_range_works() {
+        assert!(check_range(Id(11), Id(22)));
+        assert!(check_range(Id(11), Id(222)));
+        assert!(!check_range(Id(111), Id(222)));
+        assert!(check_range(Id(111), Id(2222)));
+        assert!(check_range(Id(1111), Id(22222)));
+        // only the value counts: 0101 would have looked like 4 digits
+        let id: Id = "101".parse().unwrap();
+        assert!(!check_range(id, Id(999)));
+        assert!("0101".parse::<Id>().is_err());
+    }
+
+    #[test]
+    fn has_a_pattern_test() {
+        assert!(has_a_pattern(11));
+        assert!(!has_a_pattern(1122));
+        assert!(!has_a_pattern(21122));
+        assert!(has_a_pattern(211211));
+    }
+
+    #[test]
+    fn collect_invalid_ids_for_range_test() {
+        assert_eq!(vec![11, 22], collect_invalid_ids_for_range(11, 22));
+        assert_eq!(vec![99], collect_invalid_ids_for_range(95, 115));
+        assert_eq!(vec![1010], collect_invalid_ids_for_range(998, 1012));
+        assert_eq!(
+            vec![1188511885],
+            collect_invalid_ids_for_range(1188511880, 1188511890)
+        );
+        assert_eq!(vec![222222], collect_invalid_ids_for_range(222220, 222224));
+        assert_eq!(
+            Vec::<u64>::new(),
+            collect_invalid_ids_for_range(1698522, 1698528)
+        );
+        assert_eq!(vec![446446], collect_invalid_ids_for_range(446443, 446449));
+        assert_eq!(
+            vec![38593859],
+            collect_invalid_ids_for_range(38593856, 38593862)
+        );
+        assert_eq!(vec![11, 22], collect_invalid_ids_for_range(1, 22));
+        // up to the very last u64, without overflowing
+        assert_eq!(
+            Vec::<u64>::new(),
+            collect_invalid_ids_for_range(u64::MAX - 5, u64::MAX)
+        );
+        assert_eq!(
+            vec![18446744071844674407],
+            collect_invalid_ids_for_range(18446744071844674400, u64::MAX)
+        );
+    }
+}
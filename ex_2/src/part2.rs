@@ -0,0 +1,102 @@
+// Now, an ID is invalid if it is made only of some sequence of digits
+// repeated at least twice. So, 12341234 (1234 two times),
+// 123123123 (123 three times), 1212121212 (12 five times), and 1111111 (1 seven times) are all invalid IDs.
+
+// From the same example as before:
+
+// 11-22 still has two invalid IDs, 11 and 22.
+// 95-115 now has two invalid IDs, 99 and 111.
+// 998-1012 now has two invalid IDs, 999 and 1010.
+// 1188511880-1188511890 still has one invalid ID, 1188511885.
+// 222220-222224 still has one invalid ID, 222222.
+// 1698522-1698528 still contains no invalid IDs.
+// 446443-446449 still has one invalid ID, 446446.
+// 38593856-38593862 still has one invalid ID, 38593859.
+// 565653-565659 now has one invalid ID, 565656.
+// 824824821-824824827 now has one invalid ID, 824824824.
+// 2121212118-2121212124 now has one invalid ID, 2121212121.
+// Adding up all the invalid IDs in this example produces 4174379265.
+
+use crate::digits;
+
+pub fn collect_invalid_ids_for_range(start: u64, end: u64) -> Vec<u64> {
+    let mut invalid_ids: Vec<u64> = vec![];
+    for current_number in start..=end {
+        if has_a_pattern(current_number) {
+            invalid_ids.push(current_number)
+        }
+    }
+    invalid_ids
+}
+
+fn has_a_pattern(number: u64) -> bool {
+    let mut chunk = digits::count(number) / 2;
+    while chunk > 0 {
+        if find_pattern(number, chunk) {
+            return true;
+        } else {
+            chunk -= 1
+        }
+    }
+    false
+}
+
+fn find_pattern(number: u64, chunk: u32) -> bool {
+    chunk != 0 && digits::is_repeat(number, chunk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_pattern_test() {
+        assert!(!find_pattern(112, 1));
+        assert!(!find_pattern(1123, 2));
+        assert!(!find_pattern(112112, 2));
+        assert!(find_pattern(112112, 3));
+        assert!(find_pattern(111, 1));
+        assert!(!find_pattern(1111112, 3));
+        assert!(!find_pattern(11, 0));
+    }
+    #[test]
+    fn has_a_pattern_test() {
+        assert!(has_a_pattern(11));
+        assert!(has_a_pattern(111));
+        assert!(!has_a_pattern(1122));
+        assert!(!has_a_pattern(21122));
+        assert!(has_a_pattern(211211));
+        assert!(has_a_pattern(212121));
+    }
+
+    #[test]
+    fn collect_invalid_ids_test() {
+        assert_eq!(vec![11, 22], collect_invalid_ids_for_range(11, 22));
+        assert_eq!(vec![99, 111], collect_invalid_ids_for_range(95, 115));
+        assert_eq!(vec![999, 1010], collect_invalid_ids_for_range(998, 1012));
+        assert_eq!(
+            vec![1188511885],
+            collect_invalid_ids_for_range(1188511880, 1188511890)
+        );
+        assert_eq!(vec![222222], collect_invalid_ids_for_range(222220, 222224));
+        assert_eq!(
+            Vec::<u64>::new(),
+            collect_invalid_ids_for_range(1698522, 1698528)
+        );
+        assert_eq!(vec![446446], collect_invalid_ids_for_range(446443, 446449));
+        assert_eq!(
+            vec![38593859],
+            collect_invalid_ids_for_range(38593856, 38593862)
+        );
+        assert_eq!(vec![11, 22], collect_invalid_ids_for_range(1, 22));
+        assert_eq!(vec![565656], collect_invalid_ids_for_range(565653, 565659));
+        assert_eq!(
+            vec![824824824],
+            collect_invalid_ids_for_range(824824821, 824824827)
+        );
+        assert_eq!(
+            vec![2121212121],
+            collect_invalid_ids_for_range(2121212118, 2121212124)
+        );
+    }
+}
@@ -0,0 +1,241 @@
+// What makes an ID invalid. The puzzle's two rules are about repeated blocks,
+// which `tally` and `InvalidIds` can count and jump through without looking
+// at every number; palindromes can be jumped through too. Any other rule is a
+// predicate that gets asked about every number in the range.
+
+use crate::Id;
+use crate::closed_form::pow10;
+use crate::digits;
+use std::fmt;
+use std::str::FromStr;
+
+// A block once is not a repeat, whatever k says: parsing only takes k of 2
+// or more, and built by hand AtLeast(0) and AtLeast(1) are AtLeast(2), and
+// Exactly(0) and Exactly(1) find nothing.
+#[derive(Debug, Clone, Copy)]
+pub enum IdPolicy {
+    // a block repeated exactly k times: with k = 2, 123123 but not 121212
+    Exactly(u32),
+    // a block repeated k or more times: with k = 2, both of the above
+    AtLeast(u32),
+    // reads the same both ways, at least two digits: 1221, 12321
+    Palindrome,
+    // cut into two or more equal blocks of two or more digits, one of them
+    // starts with 0, which no real ID would: 1001 is 10|01, 100200 is 10|02|00
+    // (single digits don't count, or any 0 digit would do)
+    LeadingZeroBlock,
+    // anything else, see `IdPolicy::custom`
+    Custom {
+        name: &'static str,
+        is_invalid: fn(u64) -> bool,
+    },
+}
+
+// custom policies can't compare their functions, so their names stand in
+impl PartialEq for IdPolicy {
+    fn eq(&self, other: &IdPolicy) -> bool {
+        use IdPolicy::*;
+        match (self, other) {
+            (Exactly(a), Exactly(b)) | (AtLeast(a), AtLeast(b)) => a == b,
+            (Palindrome, Palindrome) | (LeadingZeroBlock, LeadingZeroBlock) => true,
+            (Custom { name: a, .. }, Custom { name: b, .. }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for IdPolicy {}
+
+impl IdPolicy {
+    // part 1
+    pub const TWICE: IdPolicy = IdPolicy::Exactly(2);
+    // part 2
+    pub const AT_LEAST_TWICE: IdPolicy = IdPolicy::AtLeast(2);
+
+    pub const fn custom(name: &'static str, is_invalid: fn(u64) -> bool) -> IdPolicy {
+        IdPolicy::Custom { name, is_invalid }
+    }
+
+    // for the repeated block policies: are `repeats` copies of a block invalid
+    pub(crate) fn allows_repeats(&self, repeats: u32) -> Option<bool> {
+        match *self {
+            IdPolicy::Exactly(k) => Some(repeats >= 2 && repeats == k),
+            IdPolicy::AtLeast(k) => Some(repeats >= 2 && repeats >= k),
+            _ => None,
+        }
+    }
+
+    pub fn is_invalid(&self, id: u64) -> bool {
+        let digits = Id(id).digits();
+        match self {
+            IdPolicy::Exactly(_) | IdPolicy::AtLeast(_) => (1..=digits)
+                .filter(|&block| digits.is_multiple_of(block))
+                .any(|block| {
+                    self.allows_repeats(digits / block) == Some(true)
+                        && u128::from(id).is_multiple_of(multiplier(digits, block))
+                }),
+            IdPolicy::Palindrome => {
+                let text = id.to_string();
+                digits >= 2 && text.bytes().eq(text.bytes().rev())
+            }
+            IdPolicy::LeadingZeroBlock => (2..digits)
+                .filter(|&block| digits.is_multiple_of(block))
+                .any(|block| digits::blocks(id, block).any(|chunk| digits::count(chunk) < block)),
+            IdPolicy::Custom { is_invalid, .. } => is_invalid(id),
+        }
+    }
+}
+
+// 10101 for 6 digits in blocks of 2; a block times this repeats it
+pub(crate) fn multiplier(digits: u32, block: u32) -> u128 {
+    (pow10(digits) - 1) / (pow10(block) - 1)
+}
+
+impl fmt::Display for IdPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdPolicy::Exactly(k) => write!(f, "exactly:{}", k),
+            IdPolicy::AtLeast(k) => write!(f, "at-least:{}", k),
+            IdPolicy::Palindrome => write!(f, "palindrome"),
+            IdPolicy::LeadingZeroBlock => write!(f, "leading-zero-block"),
+            IdPolicy::Custom { name, .. } => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParsePolicyError(String);
+
+impl fmt::Display for ParsePolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown policy `{}`, expected exactly:K, at-least:K (K of 2 or more), \
+             palindrome, leading-zero-block or a registered name",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParsePolicyError {}
+
+// the built in policies by the names Display gives them
+impl FromStr for IdPolicy {
+    type Err = ParsePolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParsePolicyError(s.to_string());
+        let repeats = |k: &str| k.parse::<u32>().ok().filter(|&k| k >= 2).ok_or_else(error);
+        match s.split_once(':') {
+            Some(("exactly", k)) => Ok(IdPolicy::Exactly(repeats(k)?)),
+            Some(("at-least", k)) => Ok(IdPolicy::AtLeast(repeats(k)?)),
+            Some(_) => Err(error()),
+            None => match s {
+                "palindrome" => Ok(IdPolicy::Palindrome),
+                "leading-zero-block" => Ok(IdPolicy::LeadingZeroBlock),
+                _ => Err(error()),
+            },
+        }
+    }
+}
+
+// Named custom policies next to the built in ones, so a policy can be picked
+// by name, e.g. from the command line.
+#[derive(Debug, Clone, Default)]
+pub struct Policies {
+    custom: Vec<IdPolicy>,
+}
+
+impl Policies {
+    pub fn register(&mut self, name: &'static str, is_invalid: fn(u64) -> bool) {
+        self.custom.retain(|policy| policy.to_string() != name);
+        self.custom.push(IdPolicy::custom(name, is_invalid));
+    }
+
+    pub fn get(&self, name: &str) -> Result<IdPolicy, ParsePolicyError> {
+        name.parse().or_else(|err| {
+            self.custom
+                .iter()
+                .find(|policy| policy.to_string() == name)
+                .copied()
+                .ok_or(err)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid(policy: IdPolicy, ids: impl IntoIterator<Item = u64>) -> Vec<u64> {
+        ids.into_iter()
+            .filter(|&id| policy.is_invalid(id))
+            .collect()
+    }
+
+    #[test]
+    fn is_invalid_test() {
+        let ids = [11, 111, 1212, 123123, 121212, 111111, 1221, 12321, 1001, 7];
+        assert_eq!(
+            vec![11, 1212, 123123, 111111],
+            invalid(IdPolicy::TWICE, ids)
+        );
+        assert_eq!(
+            vec![111, 121212, 111111],
+            invalid(IdPolicy::Exactly(3), ids)
+        );
+        assert_eq!(
+            vec![11, 111, 1212, 123123, 121212, 111111],
+            invalid(IdPolicy::AT_LEAST_TWICE, ids)
+        );
+        assert_eq!(
+            vec![111, 121212, 111111],
+            invalid(IdPolicy::AtLeast(3), ids)
+        );
+        assert_eq!(
+            vec![11, 111, 111111, 1221, 12321, 1001],
+            invalid(IdPolicy::Palindrome, ids)
+        );
+        assert_eq!(vec![1001], invalid(IdPolicy::LeadingZeroBlock, ids));
+        assert!(IdPolicy::LeadingZeroBlock.is_invalid(100200));
+        assert!(!IdPolicy::LeadingZeroBlock.is_invalid(1010));
+    }
+
+    #[test]
+    fn fewer_than_two_repeats_test() {
+        let ids = [0, 5, 11, 111, 1212, 1234];
+        for policy in [IdPolicy::AtLeast(0), IdPolicy::AtLeast(1)] {
+            assert_eq!(invalid(IdPolicy::AT_LEAST_TWICE, ids), invalid(policy, ids));
+        }
+        for policy in [IdPolicy::Exactly(0), IdPolicy::Exactly(1)] {
+            assert!(invalid(policy, ids).is_empty());
+        }
+        for s in ["exactly:0", "exactly:1", "at-least:0", "at-least:1"] {
+            assert!(s.parse::<IdPolicy>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn parse_policy_test() {
+        for policy in [
+            IdPolicy::Exactly(3),
+            IdPolicy::AT_LEAST_TWICE,
+            IdPolicy::Palindrome,
+            IdPolicy::LeadingZeroBlock,
+        ] {
+            assert_eq!(Ok(policy), policy.to_string().parse());
+        }
+        assert!("exactly:x".parse::<IdPolicy>().is_err());
+        assert!("fizz".parse::<IdPolicy>().is_err());
+    }
+
+    #[test]
+    fn custom_policy_test() {
+        let mut policies = Policies::default();
+        policies.register("even", |id| id % 2 == 0);
+        let even = policies.get("even").unwrap();
+        assert_eq!(vec![2, 4], invalid(even, 1..=5));
+        assert_eq!(Ok(IdPolicy::Palindrome), policies.get("palindrome"));
+        assert!(policies.get("odd").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+// The input is a list of `start-end` ranges. Besides the puzzle's one long
+// comma separated line this takes ranges one per line, or a mix of both,
+// with whitespace around ranges and their dash, a trailing comma and `#`
+// comments to the end of a line:
+//
+//   11-22, 95-115,   # the first two
+//   998 - 1012
+//
+// Errors name the line and the column the bad range starts at.
+
+use crate::{BigRange, IdRange, ParseRangeError};
+use std::fmt;
+
+struct At {
+    column: usize,
+    error: ParseRangeError,
+}
+
+impl fmt::Display for At {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at column {}", self.error, self.column)
+    }
+}
+
+// every range of `input` in order, or the first bad one
+pub fn parse_range_list(input: &str) -> file_read::Result<Vec<IdRange>> {
+    scan(input, IdRange::from_bounds).into_iter().collect()
+}
+
+// the same, for bounds of any size
+pub fn parse_big_range_list(input: &str) -> file_read::Result<Vec<BigRange>> {
+    scan(input, BigRange::from_bounds).into_iter().collect()
+}
+
+// every range and every bad range, so validation can list all of them;
+// `from_bounds` gets the two sides of the dash
+pub(crate) fn scan<T>(
+    input: &str,
+    from_bounds: fn(&str, &str) -> Result<T, ParseRangeError>,
+) -> Vec<file_read::Result<T>> {
+    let mut ranges = vec![];
+    for (index, line) in input.lines().enumerate() {
+        let code = line.split('#').next().unwrap_or_default();
+        let fields: Vec<(usize, &str)> = code
+            .split(',')
+            .scan(0, |offset, field| {
+                let start = *offset;
+                *offset += field.len() + 1;
+                Some((start, field))
+            })
+            .collect();
+        // an empty field is only fine if nothing follows it: a trailing comma
+        let last = fields
+            .iter()
+            .rposition(|(_, field)| !field.trim().is_empty());
+        for (i, &(offset, field)) in fields.iter().enumerate() {
+            let token = field.trim();
+            if token.is_empty() && last.is_none_or(|last| i > last) {
+                continue;
+            }
+            let start = offset + field.len() - field.trim_start().len();
+            let column = code[..start].chars().count() + 1;
+            ranges.push(
+                range(token, from_bounds).map_err(|error| {
+                    file_read::Error::parse(index + 1, token, At { column, error })
+                }),
+            );
+        }
+    }
+    ranges
+}
+
+fn range<T>(
+    token: &str,
+    from_bounds: fn(&str, &str) -> Result<T, ParseRangeError>,
+) -> Result<T, ParseRangeError> {
+    if token.is_empty() {
+        return Err(ParseRangeError::Empty);
+    }
+    let (start, end) = token.split_once('-').ok_or(ParseRangeError::MissingDash)?;
+    from_bounds(start.trim_end(), end.trim_start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: &str) -> String {
+        parse_range_list(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn format_test() {
+        let expected = vec![
+            IdRange::new(11, 22),
+            IdRange::new(95, 115),
+            IdRange::new(998, 1012),
+        ];
+        for input in [
+            "11-22,95-115,998-1012",
+            "11-22,95-115,998-1012,\n",
+            "11-22\n95-115\n998-1012\n",
+            "  11-22 ,\t95 - 115,\n\n998-1012,",
+            "# ranges\n11-22, 95-115, # two\n998-1012 # one more\n",
+        ] {
+            assert_eq!(expected, parse_range_list(input).unwrap(), "{:?}", input);
+        }
+        assert!(parse_range_list("").unwrap().is_empty());
+        assert!(parse_range_list("# nothing\n,\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn error_test() {
+        assert_eq!(
+            "1: range has no end at column 7 in `12-`",
+            error("11-22,12-,1-2")
+        );
+        assert_eq!(
+            "2: range has no start at column 3 in `-5`",
+            error("1-2\n  -5")
+        );
+        assert_eq!(
+            "1: start and end must be integers at column 1 in `a-b`",
+            error("a-b")
+        );
+        assert_eq!(
+            "1: start and end must be separated by - at column 7 in `95`",
+            error("11-22,95")
+        );
+        // an empty range in between, not at the end
+        assert_eq!("1: range is empty at column 5 in ``", error("1-2,,3-4"));
+        assert_eq!("1: ID has a `-` sign at column 1 in `1--5`", error("1--5"));
+    }
+
+    #[test]
+    fn scan_test() {
+        let results = scan("1-2, x, 3-\n4-5", IdRange::from_bounds);
+        let errors: Vec<String> = results
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+            .map(|err| err.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "1: start and end must be separated by - at column 6 in `x`",
+                "1: range has no end at column 9 in `3-`",
+            ],
+            errors
+        );
+        assert_eq!(4, results.len());
+        // columns count characters, not bytes
+        let results = scan("é-1, x", IdRange::from_bounds);
+        assert!(
+            results[1]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("column 6")
+        );
+    }
+}
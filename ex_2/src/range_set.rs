@@ -0,0 +1,136 @@
+use crate::{IdRange, parse_range_list};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// The input's ranges as a set of IDs: sorted, with overlapping and adjacent
+// ranges merged, so that no ID is looked at (or summed) twice.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSet {
+    ranges: Vec<IdRange>,
+    // pairs of input ranges that share IDs; merging fixes them, but an
+    // input with overlaps is suspicious enough to be worth showing
+    overlaps: Vec<(IdRange, IdRange)>,
+}
+
+// a range like 22-11, which can't be merged with anything
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvertedRange(pub IdRange);
+
+impl fmt::Display for InvertedRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "range {} ends before it starts", self.0)
+    }
+}
+
+impl Error for InvertedRange {}
+
+impl RangeSet {
+    pub fn new(mut input: Vec<IdRange>) -> Result<RangeSet, InvertedRange> {
+        if let Some(inverted) = input.iter().find(|range| range.end < range.start) {
+            return Err(InvertedRange(*inverted));
+        }
+        input.sort_by_key(|range| (range.start, range.end));
+
+        let mut set = RangeSet::default();
+        // the input range that reaches furthest into the last merged range
+        let mut furthest: Option<IdRange> = None;
+        for range in input {
+            match (set.ranges.last_mut(), furthest) {
+                (Some(last), Some(reach)) if range.start <= last.end.saturating_add(1) => {
+                    if range.start <= last.end {
+                        set.overlaps.push((reach, range));
+                    }
+                    if range.end > last.end {
+                        last.end = range.end;
+                        furthest = Some(range);
+                    }
+                }
+                _ => {
+                    set.ranges.push(range);
+                    furthest = Some(range);
+                }
+            }
+        }
+        Ok(set)
+    }
+
+    // disjoint and sorted, with a gap between any two
+    pub fn ranges(&self) -> &[IdRange] {
+        &self.ranges
+    }
+
+    pub fn overlaps(&self) -> &[(IdRange, IdRange)] {
+        &self.overlaps
+    }
+
+    pub fn contains(&self, id: u64) -> bool {
+        // the last range starting at or before `id` is the only candidate
+        let after = self.ranges.partition_point(|range| range.start <= id);
+        after > 0 && id <= self.ranges[after - 1].end
+    }
+}
+
+// the whole input, see `parse_range_list` for the format
+impl FromStr for RangeSet {
+    type Err = file_read::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ranges = parse_range_list(s)?;
+        // parse_range_list already rejects inverted ranges (IdRange::from_bounds
+        // does) with a line and column, so this can't fail
+        Ok(RangeSet::new(ranges).expect("parsed ranges are not inverted"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_test() {
+        let set: RangeSet = "30-40,1-5,6-9,35-50,12-12,38-39".parse().unwrap();
+        assert_eq!(
+            &[
+                IdRange::new(1, 9),
+                IdRange::new(12, 12),
+                IdRange::new(30, 50)
+            ],
+            set.ranges()
+        );
+        assert_eq!(
+            &[
+                (IdRange::new(30, 40), IdRange::new(35, 50)),
+                (IdRange::new(35, 50), IdRange::new(38, 39))
+            ],
+            set.overlaps()
+        );
+        assert!(set.contains(1) && set.contains(7) && set.contains(12) && set.contains(50));
+        assert!(!set.contains(0) && !set.contains(10) && !set.contains(51));
+    }
+
+    #[test]
+    fn edge_test() {
+        let set = RangeSet::new(vec![
+            IdRange::new(5, u64::MAX),
+            IdRange::new(0, 4),
+            IdRange::new(u64::MAX, u64::MAX),
+        ])
+        .unwrap();
+        assert_eq!(&[IdRange::new(0, u64::MAX)], set.ranges());
+        assert_eq!(1, set.overlaps().len());
+        assert_eq!(RangeSet::default(), RangeSet::new(vec![]).unwrap());
+        assert_eq!(
+            Err(InvertedRange(IdRange::new(22, 11))),
+            RangeSet::new(vec![IdRange::new(1, 2), IdRange::new(22, 11)])
+        );
+    }
+
+    #[test]
+    fn inverted_test() {
+        assert_eq!(
+            "2: range 22-11 ends before it starts at column 1 in `22-11`",
+            "1-2,\n22-11".parse::<RangeSet>().unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,229 @@
+// The puzzle text's breakdown ("95-115 has one invalid ID, 99") for any
+// input: every range as it was given, with how many invalid IDs it has and
+// their sum, and a summary of the whole input. The ranges are reported one
+// by one, so an ID in two overlapping ranges shows up in both; the total
+// counts it once, like the answer does.
+
+use crate::{IdPolicy, IdRange, RangeSet, Tally, tally};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeReport {
+    pub range: IdRange,
+    pub tally: Tally,
+}
+
+impl RangeReport {
+    // how many IDs the range has; u128 as 0-18446744073709551615 has 2^64
+    pub fn width(&self) -> u128 {
+        u128::from(self.range.end) - u128::from(self.range.start) + 1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    // in input order
+    pub ranges: Vec<RangeReport>,
+    pub total: Tally,
+    // pairs of input ranges sharing IDs
+    pub overlaps: usize,
+}
+
+impl Report {
+    pub fn new(ranges: Vec<IdRange>, policy: IdPolicy) -> Report {
+        let reports = ranges
+            .iter()
+            .map(|&range| RangeReport {
+                range,
+                tally: tally(range, policy),
+            })
+            .collect();
+        // a range set can't be made of inverted ranges, and those have
+        // nothing to report anyway
+        let set = RangeSet::new(ranges).unwrap_or_default();
+        let total = set.ranges().iter().fold(Tally::default(), |total, &range| {
+            total + tally(range, policy)
+        });
+        Report {
+            ranges: reports,
+            total,
+            overlaps: set.overlaps().len(),
+        }
+    }
+
+    // the widest range, the first one of them if several are
+    pub fn largest(&self) -> Option<&RangeReport> {
+        self.ranges.iter().rev().max_by_key(|report| report.width())
+    }
+
+    pub fn without_invalid(&self) -> Vec<&RangeReport> {
+        self.ranges
+            .iter()
+            .filter(|report| report.tally.count == 0)
+            .collect()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for report in &self.ranges {
+            text += &format!(
+                "{}: {} IDs, {} invalid, summing to {}\n",
+                report.range,
+                report.width(),
+                report.tally.count,
+                report.tally.sum
+            );
+        }
+        text += &format!("ranges: {}\n", self.ranges.len());
+        if let Some(largest) = self.largest() {
+            text += &format!("largest: {} ({} IDs)\n", largest.range, largest.width());
+        }
+        let without: Vec<String> = self
+            .without_invalid()
+            .iter()
+            .map(|report| report.range.to_string())
+            .collect();
+        text += &format!(
+            "without invalid IDs: {}{}\n",
+            without.len(),
+            if without.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", without.join(", "))
+            }
+        );
+        if self.overlaps > 0 {
+            text += &format!(
+                "overlapping pairs: {}, their IDs count once\n",
+                self.overlaps
+            );
+        }
+        text += &format!(
+            "total: {} invalid IDs, summing to {}\n",
+            self.total.count, self.total.sum
+        );
+        text
+    }
+
+    pub const CSV_HEADER: &str = "start,end,width,count,sum";
+
+    // the ranges only, one row each; the summary doesn't fit in columns
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", Report::CSV_HEADER);
+        for report in &self.ranges {
+            csv += &format!(
+                "{},{},{},{},{}\n",
+                report.range.start,
+                report.range.end,
+                report.width(),
+                report.tally.count,
+                report.tally.sum
+            );
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        let range_json = |report: &RangeReport| {
+            format!(
+                "{{\"start\":{},\"end\":{},\"width\":{},\"count\":{},\"sum\":{}}}",
+                report.range.start,
+                report.range.end,
+                report.width(),
+                report.tally.count,
+                report.tally.sum
+            )
+        };
+        let ranges: Vec<String> = self.ranges.iter().map(range_json).collect();
+        let without: Vec<String> = self
+            .without_invalid()
+            .iter()
+            .map(|report| format!("\"{}\"", report.range))
+            .collect();
+        let largest = match self.largest() {
+            Some(largest) => format!("\"{}\"", largest.range),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"ranges\":[{}],\"summary\":{{\"ranges\":{},\"largest\":{},\"without_invalid\":[{}],\"overlaps\":{},\"count\":{},\"sum\":{}}}}}",
+            ranges.join(","),
+            self.ranges.len(),
+            largest,
+            without.join(","),
+            self.overlaps,
+            self.total.count,
+            self.total.sum
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(input: &str) -> Vec<IdRange> {
+        crate::parse_range_list(input).unwrap()
+    }
+
+    #[test]
+    fn report_test() {
+        let report = Report::new(
+            ranges("11-22,95-115,1698522-1698528,446443-446449"),
+            IdPolicy::AT_LEAST_TWICE,
+        );
+        let counts: Vec<(u128, u64, u128)> = report
+            .ranges
+            .iter()
+            .map(|report| (report.width(), report.tally.count, report.tally.sum))
+            .collect();
+        assert_eq!(
+            vec![(12, 2, 33), (21, 2, 99 + 111), (7, 0, 0), (7, 1, 446446)],
+            counts
+        );
+        assert_eq!(
+            Tally {
+                count: 5,
+                sum: 33 + 99 + 111 + 446446
+            },
+            report.total
+        );
+        assert_eq!(95, report.largest().unwrap().range.start);
+        assert_eq!(1, report.without_invalid().len());
+        assert_eq!(0, report.overlaps);
+    }
+
+    #[test]
+    fn overlapping_test() {
+        let report = Report::new(ranges("11-22,11-33"), IdPolicy::TWICE);
+        assert_eq!(2, report.ranges[0].tally.count);
+        assert_eq!(3, report.ranges[1].tally.count);
+        assert_eq!(3, report.total.count);
+        assert_eq!(1, report.overlaps);
+        // equally wide ranges: the first one
+        let report = Report::new(ranges("1-5,11-15"), IdPolicy::TWICE);
+        assert_eq!(1, report.largest().unwrap().range.start);
+        assert_eq!(None, Report::new(vec![], IdPolicy::TWICE).largest());
+    }
+
+    #[test]
+    fn render_test() {
+        let report = Report::new(ranges("11-22,1698522-1698528"), IdPolicy::TWICE);
+        assert_eq!(
+            "11-22: 12 IDs, 2 invalid, summing to 33
+1698522-1698528: 7 IDs, 0 invalid, summing to 0
+ranges: 2
+largest: 11-22 (12 IDs)
+without invalid IDs: 1 (1698522-1698528)
+total: 2 invalid IDs, summing to 33
+",
+            report.to_text()
+        );
+        assert_eq!(
+            "start,end,width,count,sum\n11,22,12,2,33\n1698522,1698528,7,0,0\n",
+            report.to_csv()
+        );
+        assert_eq!(
+            r#"{"ranges":[{"start":11,"end":22,"width":12,"count":2,"sum":33},{"start":1698522,"end":1698528,"width":7,"count":0,"sum":0}],"summary":{"ranges":2,"largest":"11-22","without_invalid":["1698522-1698528"],"overlaps":0,"count":2,"sum":33}}"#,
+            report.to_json()
+        );
+    }
+}
@@ -0,0 +1,36 @@
+pub mod part1;
+pub mod part2;
+
+use solution::Solution;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const ROOT: &'static str = env!("CARGO_MANIFEST_DIR");
+    const INPUT: &'static str = "final";
+    const EXAMPLE: &'static str = "example1";
+
+    // one bank of battery joltages per line
+    type Input = Vec<Vec<u8>>;
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> file_read::Result<Self::Input> {
+        file_read::parse_digit_grid(input)
+    }
+
+    fn part1(banks: &Self::Input) -> u32 {
+        banks
+            .iter()
+            .map(|bank| part1::find_max_2d_value(bank))
+            .sum()
+    }
+
+    fn part2(banks: &Self::Input) -> u64 {
+        banks
+            .iter()
+            .map(|bank| part2::find_max_n_digit_value(bank, 12))
+            .sum()
+    }
+}
@@ -5,25 +5,13 @@
 // In 811111111111119, you can make the largest joltage possible by turning on the batteries labeled 8 and 9, producing 89 jolts.
 // In 234234234234278, you can make 78 by turning on the last two batteries (marked 7 and 8).
 // In 818181911112111, the largest joltage you can produce is 92.
-fn main() -> std::io::Result<()> {
-    let reader = file_read::read_to_buffer_lines("final")?;
-    let mut sum = 0;
-    for line in reader {
-        let line = line?;
-        sum += find_max_2d_value(&line);
-    }
-
-    println!("sum: {}", sum);
-    Ok(())
-}
-
-fn find_max_2d_value(chain: &str) -> u32 {
-    if chain.len() <= 2 {
-        return chain.parse().expect("not a number");
+pub fn find_max_2d_value(bank: &[u8]) -> u32 {
+    let numbers: Vec<u32> = bank.iter().map(|&d| u32::from(d)).collect();
+    if numbers.len() <= 2 {
+        return numbers.iter().fold(0, |acc, d| acc * 10 + d);
     }
 
     let mut result: u32;
-    let numbers: Vec<u32> = chain.chars().filter_map(|c| c.to_digit(10)).collect();
     let mut max_num = numbers.iter().max().copied().unwrap_or(0);
     let mut index = numbers.iter().position(|&el| el == max_num).unwrap_or(0);
 
@@ -52,12 +40,16 @@ fn find_max_2d_value(chain: &str) -> u32 {
 mod test {
     use super::*;
 
+    fn bank(digits: &str) -> Vec<u8> {
+        digits.bytes().map(|b| b - b'0').collect()
+    }
+
     #[test]
     fn find_max_2d_value_test() {
-        assert_eq!(98, find_max_2d_value("987654321111111"));
-        assert_eq!(89, find_max_2d_value("811111111111119"));
-        assert_eq!(78, find_max_2d_value("234234234234278"));
-        assert_eq!(92, find_max_2d_value("818181911112111"));
-        assert_eq!(81, find_max_2d_value("81"));
+        assert_eq!(98, find_max_2d_value(&bank("987654321111111")));
+        assert_eq!(89, find_max_2d_value(&bank("811111111111119")));
+        assert_eq!(78, find_max_2d_value(&bank("234234234234278")));
+        assert_eq!(92, find_max_2d_value(&bank("818181911112111")));
+        assert_eq!(81, find_max_2d_value(&bank("81")));
     }
 }
@@ -9,24 +9,11 @@
 // In 234234234234278, the largest joltage can be found by turning on everything except a 2 battery, a 3 battery, and another 2 battery near the start to produce 434234234278.
 // In 818181911112111, the joltage 888911112111 is produced by turning on everything except some 1s near the front.
 
-fn main() -> std::io::Result<()> {
-    let reader = file_read::read_to_buffer_lines("final")?;
-    let mut sum = 0;
-    for line in reader {
-        let line = line?;
-        sum += find_max_n_digit_value(&line, 12);
-    }
-
-    println!("sum: {}", sum);
-    Ok(())
-}
-
-fn find_max_n_digit_value(chain: &str, n: usize) -> u64 {
-    let numbers: Vec<u32> = chain.chars().filter_map(|c| c.to_digit(10)).collect();
+pub fn find_max_n_digit_value(bank: &[u8], n: usize) -> u64 {
+    let numbers: Vec<u32> = bank.iter().map(|&d| u32::from(d)).collect();
 
     if numbers.len() <= n {
-        // return numbers.iter().fold(0u64, |acc, d| acc * 10 + (*d as u64)); // as a math reduce
-        return chain.parse().expect("not a number");
+        return numbers.iter().fold(0u64, |acc, d| acc * 10 + (*d as u64)); // as a math reduce
     }
 
     let mut result = 0u64; // idiomatic syntax
@@ -58,12 +45,28 @@ fn find_max_n_digit_value(chain: &str, n: usize) -> u64 {
 mod test {
     use super::*;
 
+    fn bank(digits: &str) -> Vec<u8> {
+        digits.bytes().map(|b| b - b'0').collect()
+    }
+
     #[test]
     fn find_max_n_digit_value_test() {
-        assert_eq!(987654321111, find_max_n_digit_value("987654321111111", 12));
-        assert_eq!(811111111119, find_max_n_digit_value("811111111111119", 12));
-        assert_eq!(434234234278, find_max_n_digit_value("234234234234278", 12));
-        assert_eq!(888911112111, find_max_n_digit_value("818181911112111", 12));
-        assert_eq!(81, find_max_n_digit_value("81", 12));
+        assert_eq!(
+            987654321111,
+            find_max_n_digit_value(&bank("987654321111111"), 12)
+        );
+        assert_eq!(
+            811111111119,
+            find_max_n_digit_value(&bank("811111111111119"), 12)
+        );
+        assert_eq!(
+            434234234278,
+            find_max_n_digit_value(&bank("234234234234278"), 12)
+        );
+        assert_eq!(
+            888911112111,
+            find_max_n_digit_value(&bank("818181911112111"), 12)
+        );
+        assert_eq!(81, find_max_n_digit_value(&bank("81"), 12));
     }
 }
@@ -0,0 +1,121 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+// Every variant knows which file it is about, so a failing run can be
+// reported without re-running it with extra logging.
+#[derive(Debug)]
+pub enum Error {
+    NotFound {
+        path: PathBuf,
+        // files next to `path`, i.e. the day's other inputs for a named input
+        available: Vec<String>,
+    },
+    Permission {
+        path: PathBuf,
+    },
+    // not UTF-8; `line` is 1-based
+    Decode {
+        path: PathBuf,
+        line: usize,
+    },
+    // `path` is filled in by `with_path` once the caller knows where the text
+    // came from; parsers working on a plain &str leave it empty
+    Parse {
+        path: Option<PathBuf>,
+        line: usize,
+        content: String,
+        message: String,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl Error {
+    pub fn parse(line: usize, content: &str, message: impl fmt::Display) -> Error {
+        Error::Parse {
+            path: None,
+            line,
+            content: content.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn with_path(self, source_path: impl AsRef<Path>) -> Error {
+        match self {
+            Error::Parse {
+                path: None,
+                line,
+                content,
+                message,
+            } => Error::Parse {
+                path: Some(source_path.as_ref().to_path_buf()),
+                line,
+                content,
+                message,
+            },
+            other => other,
+        }
+    }
+
+    pub(crate) fn from_io(path: &Path, err: io::Error) -> Error {
+        let path = path.to_path_buf();
+        match err.kind() {
+            io::ErrorKind::NotFound => {
+                let available = path
+                    .parent()
+                    .and_then(|dir| crate::list_dir(dir).ok())
+                    .unwrap_or_default();
+                Error::NotFound { path, available }
+            }
+            io::ErrorKind::PermissionDenied => Error::Permission { path },
+            _ => Error::Io { path, source: err },
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotFound { path, available } => {
+                write!(f, "input {} not found", path.display())?;
+                if available.is_empty() {
+                    write!(f, "; no inputs next to it")
+                } else {
+                    write!(f, "; available: {}", available.join(", "))
+                }
+            }
+            Error::Permission { path } => {
+                write!(f, "no permission to read {}", path.display())
+            }
+            Error::Decode { path, line } => {
+                write!(f, "{}:{}: not valid UTF-8", path.display(), line)
+            }
+            Error::Parse {
+                path,
+                line,
+                content,
+                message,
+            } => {
+                if let Some(path) = path {
+                    write!(f, "{}:", path.display())?;
+                }
+                write!(f, "{}: {} in `{}`", line, message, content)
+            }
+            Error::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -1,25 +1,209 @@
-use std::fs::File;
-use std::env;
-use std::io::{self, BufRead, BufReader, Lines};
-use std::path::PathBuf;
+mod error;
+mod parse;
 
-fn input_root() -> PathBuf {
-    env::var("CARGO_MANIFEST_DIR")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")))
+pub use error::{Error, Result};
+pub use parse::{parse_digit_grid, parse_lines, split_records};
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static TRACING: AtomicBool = AtomicBool::new(false);
+
+// Off by default; when on, every open and read is reported on stderr.
+pub fn set_tracing(enabled: bool) {
+    TRACING.store(enabled, Ordering::Relaxed);
+}
+
+fn trace(message: fmt::Arguments) {
+    if TRACING.load(Ordering::Relaxed) {
+        eprintln!("file_read: {}", message);
+    }
+}
+
+// every day keeps its inputs under <crate>/src/input, so callers pass their own
+// env!("CARGO_MANIFEST_DIR") as root; the runtime one points at whatever binary cargo ran
+fn input_path(root: &Path, filename: &str) -> PathBuf {
+    root.join("src").join("input").join(filename)
+}
+
+fn file_open(path: &Path) -> Result<File> {
+    trace(format_args!("opening {}", path.display()));
+    File::open(path).map_err(|err| Error::from_io(path, err))
 }
 
-fn input_path(filename: &str) -> PathBuf {
-    input_root().join("src").join("input").join(filename)
+// names of the files in root/src/input, sorted
+pub fn available_inputs(root: impl AsRef<Path>) -> Result<Vec<String>> {
+    let dir = root.as_ref().join("src").join("input");
+    list_dir(&dir).map_err(|err| Error::from_io(&dir, err))
 }
 
-fn file_open(file_name: &str) -> io::Result<File> {
-    dbg!(input_path(file_name));
-    File::open(input_path(file_name))
+fn list_dir(dir: &Path) -> io::Result<Vec<String>> {
+    let mut names = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            names.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+// Where an input comes from once a command line value has been resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    // "-" is stdin, anything that looks like a path (has a separator or starts
+    // with '.') is taken as is, and a bare name is looked up in root/src/input
+    pub fn resolve(root: impl AsRef<Path>, spec: &str) -> Source {
+        if spec == "-" {
+            Source::Stdin
+        } else if spec.starts_with('.') || spec.contains(['/', std::path::MAIN_SEPARATOR]) {
+            Source::File(PathBuf::from(spec))
+        } else {
+            Source::File(input_path(root.as_ref(), spec))
+        }
+    }
+
+    // what errors report as the path; stdin has none so it gets a placeholder
+    pub fn path(&self) -> &Path {
+        match self {
+            Source::Stdin => Path::new("<stdin>"),
+            Source::File(path) => path,
+        }
+    }
+
+    fn reader(&self) -> Result<Box<dyn BufRead>> {
+        Ok(match self {
+            Source::Stdin => Box::new(BufReader::new(io::stdin())),
+            Source::File(path) => Box::new(BufReader::new(file_open(path)?)),
+        })
+    }
+
+    pub fn read_to_string(&self) -> Result<String> {
+        let mut bytes = vec![];
+        self.reader()?
+            .read_to_end(&mut bytes)
+            .map_err(|err| Error::from_io(self.path(), err))?;
+        trace(format_args!("read {} bytes from {}", bytes.len(), self));
+        String::from_utf8(bytes).map_err(|err| {
+            let valid = &err.as_bytes()[..err.utf8_error().valid_up_to()];
+            Error::Decode {
+                path: self.path().to_path_buf(),
+                line: valid.iter().filter(|&&byte| byte == b'\n').count() + 1,
+            }
+        })
+    }
+
+    pub fn lines(&self) -> Result<Lines> {
+        Ok(Lines {
+            path: self.path().to_path_buf(),
+            line: 0,
+            inner: self.reader()?.lines(),
+        })
+    }
 }
 
-// usage: for line in read_to_buffer_lines("input.txt")? { ... }
-pub fn read_to_buffer_lines(name: &str) -> io::Result<Lines<BufReader<File>>> {
-    let file = file_open(name)?;
-    Ok(BufReader::new(file).lines())
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path().display())
+    }
+}
+
+// Like io::Lines, but errors say which file and line they happened on.
+pub struct Lines {
+    path: PathBuf,
+    line: usize,
+    inner: io::Lines<Box<dyn BufRead>>,
+}
+
+impl Iterator for Lines {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.inner.next()?;
+        self.line += 1;
+        Some(next.map_err(|err| match err.kind() {
+            io::ErrorKind::InvalidData => Error::Decode {
+                path: self.path.clone(),
+                line: self.line,
+            },
+            _ => Error::from_io(&self.path, err),
+        }))
+    }
+}
+
+// usage: for line in read_to_buffer_lines(env!("CARGO_MANIFEST_DIR"), "input.txt")? { ... }
+pub fn read_to_buffer_lines(root: impl AsRef<Path>, name: &str) -> Result<Lines> {
+    Source::resolve(root, name).lines()
+}
+
+pub fn read_to_string(root: impl AsRef<Path>, name: &str) -> Result<String> {
+    Source::resolve(root, name).read_to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOT: &str = env!("CARGO_MANIFEST_DIR");
+
+    #[test]
+    fn resolve_test() {
+        assert_eq!(Source::Stdin, Source::resolve("/day", "-"));
+        assert_eq!(
+            Source::File(PathBuf::from("/day/src/input/final")),
+            Source::resolve("/day", "final")
+        );
+        assert_eq!(
+            Source::File(PathBuf::from("./final")),
+            Source::resolve("/day", "./final")
+        );
+        assert_eq!(
+            Source::File(PathBuf::from("/tmp/final")),
+            Source::resolve("/day", "/tmp/final")
+        );
+    }
+
+    #[test]
+    fn input_not_found_test() {
+        match Source::resolve(ROOT, "missing").read_to_string() {
+            Err(Error::NotFound { path, available }) => {
+                assert!(path.ends_with("src/input/missing"));
+                assert_eq!(vec!["example", "not_utf8"], available);
+            }
+            other => panic!("expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decode_error_has_line_test() {
+        match read_to_string(ROOT, "not_utf8") {
+            Err(Error::Decode { path, line }) => {
+                assert!(path.ends_with("src/input/not_utf8"));
+                assert_eq!(2, line);
+            }
+            other => panic!("expected Decode, got {:?}", other),
+        }
+        let lines: Vec<_> = read_to_buffer_lines(ROOT, "not_utf8").unwrap().collect();
+        assert_eq!("fine", lines[0].as_ref().unwrap());
+        assert!(matches!(lines[1], Err(Error::Decode { line: 2, .. })));
+    }
+
+    #[test]
+    fn parse_error_display_test() {
+        let err = Error::parse(3, "F27", "unknown direction");
+        assert_eq!("3: unknown direction in `F27`", err.to_string());
+        assert_eq!(
+            "day/input:3: unknown direction in `F27`",
+            err.with_path("day/input").to_string()
+        );
+    }
 }
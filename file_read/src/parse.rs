@@ -0,0 +1,118 @@
+use crate::{Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+// Helpers for turning puzzle text into typed values. Line numbers in errors
+// are 1-based, like an editor shows them.
+
+// one value per line
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            line.parse()
+                .map_err(|err| Error::parse(index + 1, line, err))
+        })
+        .collect()
+}
+
+// any number of values per line separated by `delimiter`; a trailing
+// delimiter at the end of a line is fine, an empty record in between is not
+pub fn split_records<T>(input: &str, delimiter: char) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let mut records = vec![];
+    for (index, line) in input.lines().enumerate() {
+        for record in line.split_terminator(delimiter) {
+            let value = record
+                .parse()
+                .map_err(|err| Error::parse(index + 1, record, err))?;
+            records.push(value);
+        }
+    }
+    Ok(records)
+}
+
+// every line is a row of single decimal digits
+pub fn parse_digit_grid(input: &str) -> Result<Vec<Vec<u8>>> {
+    parse_lines::<DigitRow>(input).map(|rows| rows.into_iter().map(|row| row.0).collect())
+}
+
+struct DigitRow(Vec<u8>);
+
+struct NotADigit {
+    column: usize,
+    found: char,
+}
+
+impl fmt::Display for NotADigit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` at column {} is not a digit",
+            self.found, self.column
+        )
+    }
+}
+
+impl FromStr for DigitRow {
+    type Err = NotADigit;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        s.chars()
+            .enumerate()
+            .map(|(index, c)| {
+                c.to_digit(10).map(|digit| digit as u8).ok_or(NotADigit {
+                    column: index + 1,
+                    found: c,
+                })
+            })
+            .collect::<std::result::Result<_, _>>()
+            .map(DigitRow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_lines_test() {
+        assert_eq!(
+            vec![1, 22, 333],
+            parse_lines::<u32>("1\n22\n333\n").unwrap()
+        );
+        let err = parse_lines::<u32>("1\nx2\n3").unwrap_err();
+        assert!(matches!(&err, Error::Parse { line: 2, content, .. } if content == "x2"));
+    }
+
+    #[test]
+    fn split_records_test() {
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            split_records::<u8>("1,2,\n3,4", ',').unwrap()
+        );
+        let err = split_records::<u8>("1,2\n3,,4", ',').unwrap_err();
+        assert!(matches!(&err, Error::Parse { line: 2, content, .. } if content.is_empty()));
+    }
+
+    #[test]
+    fn parse_digit_grid_test() {
+        assert_eq!(
+            vec![vec![1, 2], vec![9, 0]],
+            parse_digit_grid("12\n90").unwrap()
+        );
+        let err = parse_digit_grid("12\n9a0").unwrap_err();
+        assert_eq!(
+            "2: `a` at column 2 is not a digit in `9a0`",
+            err.to_string()
+        );
+    }
+}
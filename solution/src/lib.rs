@@ -0,0 +1,67 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// Every day implements this: parse the raw input once into a typed model,
+// then answer both parts from it. Reading files is the caller's job.
+pub trait Solution {
+    const DAY: u8;
+    // directory whose src/input holds this day's inputs, i.e. env!("CARGO_MANIFEST_DIR")
+    const ROOT: &'static str;
+    const INPUT: &'static str;
+    const EXAMPLE: &'static str;
+
+    type Input;
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    // errors come from the file_read parse helpers so they carry the line
+    fn parse(input: &str) -> file_read::Result<Self::Input>;
+
+    // every problem in the input; days whose parser stops at the first error
+    // just report that one
+    fn validate(input: &str) -> Vec<file_read::Error> {
+        Self::parse(input).err().into_iter().collect()
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsePartError;
+
+impl fmt::Display for ParsePartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "part must be 1 or 2")
+    }
+}
+
+impl Error for ParsePartError {}
+
+impl FromStr for Part {
+    type Err = ParsePartError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(ParsePartError),
+        }
+    }
+}